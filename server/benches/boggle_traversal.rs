@@ -0,0 +1,92 @@
+//! Compares the path-stack Boggle search, which looks every word up from
+//! the root of the trie, with the cursor search.
+//!
+//! Run with `cargo bench --bench boggle_traversal`.
+
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use rust_game_ai::games::boggle_like::cursor_analyst::CursorAnalyst;
+use rust_game_ai::games::boggle_like::generator::{BoardGenerator, DiceSet};
+use rust_game_ai::games::boggle_like::trie::WordTrie;
+use rust_game_ai::games::boggle_like::{BoggleLikeAnalyst, LetterGrid, LetterGridNxM, StateNxMAnalyst};
+
+const DICTIONARY_BOARDS: usize = 2_000;
+const WALKS_PER_BOARD: usize = 50;
+const BENCHMARK_BOARDS: usize = 100;
+
+/// There's no word list in the repository, so the dictionary is made of
+/// random walks on other boards rolled from the same dice. Like real words,
+/// they share a lot of prefixes with each other and with the boards being
+/// solved.
+fn build_dictionary(dice_set: DiceSet, rng: &mut StdRng) -> WordTrie {
+    let mut generator = BoardGenerator::new(dice_set, rng.gen());
+    let mut words: Vec<String> = vec![];
+
+    for _ in 0..DICTIONARY_BOARDS {
+        let grid = generator.roll();
+        let cells = grid.grid_cells();
+
+        for _ in 0..WALKS_PER_BOARD {
+            let length = rng.gen_range(3, 10);
+            let mut path = vec![*cells.choose(rng).unwrap()];
+            while path.len() < length {
+                let last = path[path.len() - 1];
+                let next = grid.neighbors(&last)
+                    .into_iter()
+                    .filter(|cell| !path.contains(cell))
+                    .collect::<Vec<_>>();
+                match next.choose(rng) {
+                    Some(cell) => path.push(*cell),
+                    None => break,
+                }
+            }
+
+            let word: Vec<u8> = path.iter()
+                .flat_map(|cell| grid.get_cell_value(cell.col, cell.row).to_vec())
+                .collect();
+            words.push(String::from_utf8(word).unwrap());
+        }
+    }
+
+    WordTrie::from_words_owned(&words)
+}
+
+fn time_analyst<A: BoggleLikeAnalyst>(
+    new_analyst: fn() -> A,
+    boards: &[LetterGridNxM],
+    dictionary: &WordTrie,
+) -> Duration {
+    let start = Instant::now();
+    for grid in boards {
+        let mut analyst = new_analyst();
+        analyst.find_all_valid_words(grid, dictionary);
+    }
+
+    start.elapsed()
+}
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(2021);
+
+    for dice_set in [DiceSet::NewBoggle, DiceSet::BigBoggle, DiceSet::SuperBigBoggle].iter() {
+        let dictionary = build_dictionary(*dice_set, &mut rng);
+        let mut generator = BoardGenerator::new(*dice_set, rng.gen());
+        let boards: Vec<LetterGridNxM> = (0..BENCHMARK_BOARDS).map(|_| generator.roll()).collect();
+
+        let path_stack = time_analyst(StateNxMAnalyst::new, &boards, &dictionary);
+        let cursor = time_analyst(CursorAnalyst::new, &boards, &dictionary);
+
+        println!(
+            "{:?} ({} boards): path stack {:?}, cursor {:?}, {:.1}x faster",
+            dice_set,
+            boards.len(),
+            path_stack,
+            cursor,
+            path_stack.as_secs_f64() / cursor.as_secs_f64(),
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Compares the memory use and lookup speed of `WordTrie` and `Dawg`.
+//!
+//! Run with `cargo bench --bench dictionary_size`. Set `DICTIONARY` to a word
+//! list to measure a real one; otherwise a synthetic list is used.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use rust_game_ai::games::boggle_like::dawg::Dawg;
+use rust_game_ai::games::boggle_like::read_word_list;
+use rust_game_ai::games::boggle_like::trie::WordTrie;
+
+const SYNTHETIC_STEMS: usize = 30_000;
+const SUFFIXES: [&str; 8] = ["", "S", "ED", "ING", "ER", "ERS", "LY", "NESS"];
+const QUERIES: usize = 1_000_000;
+
+/// Keeps a count of the bytes currently allocated.
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Words made of random stems with common English endings, so that like a
+/// real word list they share both prefixes and suffixes.
+fn synthetic_words(rng: &mut StdRng) -> Vec<String> {
+    let consonants = b"BCDFGHKLMNPRSTVW";
+    let vowels = b"AEIOU";
+    let mut words: Vec<String> = vec![];
+
+    for _ in 0..SYNTHETIC_STEMS {
+        let length = rng.gen_range(3, 8);
+        let stem: String = (0..length)
+            .map(|i| {
+                let letters: &[u8] = if i % 2 == 0 { consonants } else { vowels };
+                *letters.choose(rng).unwrap() as char
+            })
+            .collect();
+
+        for suffix in SUFFIXES.iter() {
+            if suffix.is_empty() || rng.gen_bool(0.4) {
+                words.push(format!("{}{}", stem, suffix));
+            }
+        }
+    }
+
+    words
+}
+
+/// Words, prefixes of words and misspellings, in random order.
+fn queries(words: &[String], rng: &mut StdRng) -> Vec<Vec<u8>> {
+    (0..QUERIES)
+        .map(|_| {
+            let mut query = words.choose(rng).unwrap().as_bytes().to_vec();
+            match rng.gen_range(0, 3) {
+                0 => query.truncate(rng.gen_range(1, query.len() + 1)),
+                1 => {
+                    let i = rng.gen_range(0, query.len());
+                    query[i] = rng.gen_range(b'A', b'Z' + 1);
+                },
+                _ => {},
+            }
+            query
+        })
+        .collect()
+}
+
+fn measure<T>(build: impl FnOnce() -> T) -> (T, usize, Duration) {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let built = build();
+    let elapsed = start.elapsed();
+
+    (built, ALLOCATED.load(Ordering::Relaxed) - before, elapsed)
+}
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(2021);
+    let words = match env::var("DICTIONARY") {
+        Ok(path) => read_word_list(&path).expect("couldn't read DICTIONARY"),
+        Err(_) => synthetic_words(&mut rng),
+    };
+    let queries = queries(&words, &mut rng);
+    println!("{} words, {} lookups", words.len(), queries.len());
+
+    let (trie, trie_bytes, trie_build) = measure(|| WordTrie::from_words_owned(&words));
+    let (dawg, dawg_bytes, dawg_build) = measure(|| Dawg::from_words_owned(&words));
+    let file = dawg.to_bytes();
+    let (_, _, dawg_load) = measure(|| Dawg::from_bytes(&file).unwrap());
+
+    println!("WordTrie: {} KiB, built in {:?}", trie_bytes / 1024, trie_build);
+    println!(
+        "Dawg: {} KiB ({} nodes, {} edges), built in {:?}, {} KiB file loads in {:?}",
+        dawg_bytes / 1024,
+        dawg.node_count(),
+        dawg.edge_count(),
+        dawg_build,
+        file.len() / 1024,
+        dawg_load,
+    );
+
+    let start = Instant::now();
+    let trie_words = queries.iter().filter(|query| trie.find(query).is_word).count();
+    let trie_lookups = start.elapsed();
+
+    let start = Instant::now();
+    let dawg_words = queries.iter().filter(|query| dawg.find(query).is_word).count();
+    let dawg_lookups = start.elapsed();
+
+    assert_eq!(trie_words, dawg_words);
+    println!(
+        "lookups: WordTrie {:?}, Dawg {:?} ({:.1}x the memory, {:.2}x the speed)",
+        trie_lookups,
+        dawg_lookups,
+        trie_bytes as f64 / dawg_bytes as f64,
+        trie_lookups.as_secs_f64() / dawg_lookups.as_secs_f64(),
+    );
+}
@@ -0,0 +1,71 @@
+//! Times `WordIndexN` queries, both working out the set of matching word ids
+//! and turning it into a list of words.
+//!
+//! Run with `cargo bench --bench word_query`. Set `WORD_LIST` to a word list
+//! to query a real one, such as the Scrabble list; otherwise random five
+//! letter words are used.
+
+use std::env;
+use std::time::Instant;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use rust_game_ai::games::boggle_like::read_word_list;
+use rust_game_ai::word::query::parse_query;
+use rust_game_ai::word::word_index::WordIndexN;
+
+const WORD_LENGTH: usize = 5;
+// about the number of five letter words in the Scrabble list
+const SYNTHETIC_WORDS: usize = 12_000;
+const RUNS: u32 = 10_000;
+const QUERIES: [&str; 5] = [
+    "s1 & a3 & t5 & (h2 | h4)",
+    "s1 & a3 & t5 & (h2 | h4) & !e & contains(r)",
+    "!a & !e & !i & !o & !u",
+    "(c1 | s1) & r2 & !n & contains(e)",
+    "a | e | i | o | u",
+];
+
+fn synthetic_words(rng: &mut StdRng) -> Vec<String> {
+    (0..SYNTHETIC_WORDS)
+        .map(|_| (0..WORD_LENGTH).map(|_| rng.gen_range(b'A', b'Z' + 1) as char).collect())
+        .collect()
+}
+
+fn main() {
+    let words = match env::var("WORD_LIST") {
+        Ok(path) => read_word_list(&path).expect("couldn't read WORD_LIST"),
+        Err(_) => synthetic_words(&mut StdRng::seed_from_u64(2021)),
+    };
+    let words: Vec<String> = words.into_iter()
+        .filter(|word| word.chars().count() == WORD_LENGTH)
+        .collect();
+    let index = WordIndexN::new(WORD_LENGTH, words).expect("couldn't index the words");
+    println!("{} words", index.word_count());
+
+    for query in QUERIES.iter() {
+        let matches = index.query(query).unwrap().len();
+        let parsed = parse_query(query).unwrap();
+
+        let start = Instant::now();
+        for _ in 0..RUNS {
+            assert_eq!(index.matching_set(&parsed).len(), matches);
+        }
+        let set_elapsed = start.elapsed();
+
+        let start = Instant::now();
+        for _ in 0..RUNS {
+            assert_eq!(index.query(query).unwrap().len(), matches);
+        }
+        let words_elapsed = start.elapsed();
+
+        println!(
+            "{:>10?} as a set, {:>10?} as words, {:>5} matches: {}",
+            set_elapsed / RUNS,
+            words_elapsed / RUNS,
+            matches,
+            query,
+        );
+    }
+}
@@ -2,7 +2,18 @@ use actix_web::{web, Error, HttpRequest, HttpResponse, Responder};
 use futures::future::{ready, Ready};
 use serde::{Deserialize, Serialize};
 
+use crate::games::boggle_like::GridCell;
+use crate::games::boggle_like::analyst::BoggleAnalyst;
+use crate::games::boggle_like::solver::{ScoredWord, ScoringRules, SolveOptions};
 use crate::games::shut_the_box::{ShutTheBoxAnalyst, State};
+use crate::peg_solitaire::analyst::PegSolitaireAnalyst;
+use crate::peg_solitaire::peg_board::PegMove;
+use crate::word::analyst::{WordAnalysisError, WordAnalyst, WordleAnalyst};
+use crate::word::guess_ranker::{GuessSuggestion, RankingStrategy};
+
+const DEFAULT_PAGE_SIZE: usize = 100;
+const MAX_PAGE_SIZE: usize = 1000;
+const DEFAULT_SUGGESTION_COUNT: usize = 10;
 
 #[derive(Deserialize)]
 pub struct BestActionRequest {
@@ -55,6 +66,313 @@ pub async fn find_best_action(info: web::Json<BestActionRequest>, data: web::Dat
 }
 
 
+#[derive(Deserialize)]
+pub struct PegPositionRequest {
+    board: String,
+    pegs: Vec<bool>,
+    target_hole: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct PegMoveResponse {
+    from: usize,
+    over: usize,
+    to: usize,
+}
+
+impl PegMoveResponse {
+    fn from_move(action: &PegMove) -> PegMoveResponse {
+        PegMoveResponse {
+            from: action.from,
+            over: action.over,
+            to: action.to,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PegPositionResponse {
+    /// `null` when the search ran out of time before it could tell.
+    solvable: Option<bool>,
+    min_reachable_pegs: Option<u32>,
+    winning_moves: Vec<PegMoveResponse>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+    /// Where in a malformed query the problem is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<usize>,
+}
+
+impl ErrorResponse {
+    fn new(error: String) -> ErrorResponse {
+        ErrorResponse {
+            error,
+            position: None,
+        }
+    }
+
+    fn from_word_analysis(e: &WordAnalysisError) -> ErrorResponse {
+        ErrorResponse {
+            error: e.to_string(),
+            position: match e {
+                WordAnalysisError::InvalidQuery(query_error) => Some(query_error.position),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Answers request bodies that aren't the JSON a route expects with an
+/// `ErrorResponse`, rather than plain text.
+pub fn json_config() -> web::JsonConfig {
+    web::JsonConfig::default()
+        .error_handler(|e, _req| {
+            let response = HttpResponse::BadRequest().json(ErrorResponse::new(e.to_string()));
+            actix_web::error::InternalError::from_response(e, response).into()
+        })
+}
+
+pub async fn evaluate_peg_position(info: web::Json<PegPositionRequest>, data: web::Data<PegSolitaireAnalyst>) -> HttpResponse {
+    println!("called evaluate_peg_position {:?} {:?} {:?}", info.board, info.pegs, info.target_hole);
+    match data.evaluate(&info.board, &info.pegs, info.target_hole) {
+        Ok(evaluation) => {
+            HttpResponse::Ok().json(PegPositionResponse {
+                solvable: evaluation.is_solvable,
+                min_reachable_pegs: evaluation.min_reachable_pegs,
+                winning_moves: evaluation.winning_moves.iter()
+                    .map(PegMoveResponse::from_move)
+                    .collect(),
+            })
+        },
+        Err(e) => {
+            HttpResponse::BadRequest().json(ErrorResponse::new(e.to_string()))
+        },
+    }
+}
+
+#[derive(Deserialize)]
+pub struct BoggleSolveRequest {
+    grid: String,
+    dictionary: Option<String>,
+    min_word_length: Option<usize>,
+    scoring: Option<String>,
+    wrap: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct GridCellResponse {
+    col: usize,
+    row: usize,
+}
+
+#[derive(Serialize)]
+struct BoggleWordResponse {
+    word: String,
+    path: Vec<GridCellResponse>,
+    score: u32,
+}
+
+impl BoggleWordResponse {
+    fn from_word(word: &ScoredWord) -> BoggleWordResponse {
+        BoggleWordResponse {
+            word: word.word.clone(),
+            path: word.path.iter()
+                .map(|cell: &GridCell| GridCellResponse { col: cell.col, row: cell.row })
+                .collect(),
+            score: word.score,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct BoggleSolveResponse {
+    words: Vec<BoggleWordResponse>,
+    total_score: u32,
+}
+
+pub async fn solve_boggle(info: web::Json<BoggleSolveRequest>, data: web::Data<BoggleAnalyst>) -> HttpResponse {
+    println!("called solve_boggle {:?} {:?} {:?} {:?} {:?}", info.grid, info.dictionary, info.min_word_length, info.scoring, info.wrap);
+    let defaults = SolveOptions::default();
+    let scoring = match info.scoring.as_deref() {
+        Some(name) => match ScoringRules::from_name(name) {
+            Some(scoring) => scoring,
+            None => {
+                return HttpResponse::BadRequest().json(ErrorResponse::new(format!("unknown scoring '{}'", name)));
+            },
+        },
+        None => defaults.scoring,
+    };
+    let options = SolveOptions {
+        min_word_length: info.min_word_length.unwrap_or(defaults.min_word_length),
+        scoring,
+        wrap: info.wrap.unwrap_or(defaults.wrap),
+    };
+
+    match data.solve(&info.grid, info.dictionary.as_deref(), &options) {
+        Ok(solution) => {
+            HttpResponse::Ok().json(BoggleSolveResponse {
+                words: solution.words.iter()
+                    .map(BoggleWordResponse::from_word)
+                    .collect(),
+                total_score: solution.total_score,
+            })
+        },
+        Err(e) => {
+            HttpResponse::BadRequest().json(ErrorResponse::new(e.to_string()))
+        },
+    }
+}
+
+/// One page of a list of words. Pages count from 1, and a page past the
+/// end is empty.
+#[derive(Serialize)]
+struct WordPageResponse {
+    words: Vec<String>,
+    total: usize,
+    page: usize,
+    page_size: usize,
+    page_count: usize,
+}
+
+fn paginate(words: Vec<String>, page: Option<usize>, page_size: Option<usize>) -> Result<WordPageResponse, String> {
+    let page = page.unwrap_or(1);
+    let page_size = page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    if page == 0 {
+        return Err("pages count from 1".to_owned());
+    }
+    if page_size == 0 || page_size > MAX_PAGE_SIZE {
+        return Err(format!("page_size must be from 1 to {}", MAX_PAGE_SIZE));
+    }
+
+    let total = words.len();
+    Ok(WordPageResponse {
+        words: words.into_iter()
+            .skip((page - 1).saturating_mul(page_size))
+            .take(page_size)
+            .collect(),
+        total,
+        page,
+        page_size,
+        page_count: total.div_ceil(page_size),
+    })
+}
+
+fn page_or_bad_request(words: Vec<String>, page: Option<usize>, page_size: Option<usize>) -> HttpResponse {
+    match paginate(words, page, page_size) {
+        Ok(page) => HttpResponse::Ok().json(page),
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse::new(e)),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct WordQueryRequest {
+    query: String,
+    length: usize,
+    dictionary: Option<String>,
+    page: Option<usize>,
+    page_size: Option<usize>,
+}
+
+pub async fn query_words(info: web::Json<WordQueryRequest>, data: web::Data<WordAnalyst>) -> HttpResponse {
+    println!("called query_words {:?} {:?} {:?} {:?} {:?}", info.query, info.length, info.dictionary, info.page, info.page_size);
+    match data.query(&info.query, info.length, info.dictionary.as_deref()) {
+        Ok(words) => page_or_bad_request(words, info.page, info.page_size),
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse::from_word_analysis(&e)),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct WordleGuessRequest {
+    guess: String,
+    /// One character per letter: g for green, y for yellow and b, x, - or .
+    /// for gray.
+    feedback: String,
+}
+
+fn to_history(guesses: &[WordleGuessRequest]) -> Vec<(String, String)> {
+    guesses.iter()
+        .map(|guess| (guess.guess.clone(), guess.feedback.clone()))
+        .collect()
+}
+
+#[derive(Deserialize)]
+pub struct WordleCandidatesRequest {
+    history: Vec<WordleGuessRequest>,
+    page: Option<usize>,
+    page_size: Option<usize>,
+}
+
+pub async fn wordle_candidates(info: web::Json<WordleCandidatesRequest>, data: web::Data<WordleAnalyst>) -> HttpResponse {
+    println!("called wordle_candidates {:?} {:?} {:?}", info.history.len(), info.page, info.page_size);
+    match data.candidates(&to_history(&info.history)) {
+        Ok(candidates) => page_or_bad_request(candidates, info.page, info.page_size),
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse::from_word_analysis(&e)),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct WordleSuggestRequest {
+    history: Vec<WordleGuessRequest>,
+    strategy: Option<String>,
+    count: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct GuessSuggestionResponse {
+    guess: String,
+    entropy: f64,
+    expected_remaining: f64,
+    worst_case_remaining: usize,
+    is_candidate: bool,
+}
+
+impl GuessSuggestionResponse {
+    fn from_suggestion(suggestion: &GuessSuggestion) -> GuessSuggestionResponse {
+        GuessSuggestionResponse {
+            guess: suggestion.guess.clone(),
+            entropy: suggestion.entropy,
+            expected_remaining: suggestion.expected_remaining,
+            worst_case_remaining: suggestion.worst_case_remaining,
+            is_candidate: suggestion.is_candidate,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct WordleSuggestResponse {
+    candidate_count: usize,
+    suggestions: Vec<GuessSuggestionResponse>,
+}
+
+pub async fn wordle_suggest(info: web::Json<WordleSuggestRequest>, data: web::Data<WordleAnalyst>) -> HttpResponse {
+    println!("called wordle_suggest {:?} {:?} {:?}", info.history.len(), info.strategy, info.count);
+    let strategy = match info.strategy.as_deref() {
+        Some(name) => match RankingStrategy::from_name(name) {
+            Some(strategy) => strategy,
+            None => {
+                return HttpResponse::BadRequest().json(ErrorResponse::new(format!("unknown strategy '{}'", name)));
+            },
+        },
+        None => RankingStrategy::Entropy,
+    };
+    let count = info.count.unwrap_or(DEFAULT_SUGGESTION_COUNT).min(MAX_PAGE_SIZE);
+
+    match data.suggest(&to_history(&info.history), strategy, count) {
+        Ok((candidate_count, suggestions)) => {
+            HttpResponse::Ok().json(WordleSuggestResponse {
+                candidate_count,
+                suggestions: suggestions.iter()
+                    .map(GuessSuggestionResponse::from_suggestion)
+                    .collect(),
+            })
+        },
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse::from_word_analysis(&e)),
+    }
+}
+
 
 // #[post("/echo")]
 // pub async fn echo(req_body: String) -> impl Responder {
@@ -64,3 +382,25 @@ pub async fn find_best_action(info: web::Json<BestActionRequest>, data: web::Dat
 // pub async fn manual_hello() -> impl Responder {
 //     HttpResponse::Ok().body("Hey there!")
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginate() {
+        let words: Vec<String> = (0..5).map(|i| i.to_string()).collect();
+
+        let page = paginate(words.clone(), Some(2), Some(2)).unwrap();
+        assert_eq!(page.words, vec!["2", "3"]);
+        assert_eq!((page.total, page.page_count), (5, 3));
+
+        assert_eq!(paginate(words.clone(), Some(3), Some(2)).unwrap().words, vec!["4"]);
+        assert!(paginate(words.clone(), Some(4), Some(2)).unwrap().words.is_empty());
+        assert_eq!(paginate(words.clone(), None, None).unwrap().words.len(), 5);
+
+        assert!(paginate(words.clone(), Some(0), None).is_err());
+        assert!(paginate(words.clone(), None, Some(0)).is_err());
+        assert!(paginate(words, None, Some(MAX_PAGE_SIZE + 1)).is_err());
+    }
+}
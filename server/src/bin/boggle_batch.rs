@@ -0,0 +1,122 @@
+//! Solves every board in a file against one dictionary.
+//!
+//! ```text
+//! boggle_batch <boards file> --dictionary <word list> [--format json|csv]
+//!     [--threads N] [--min-word-length N] [--scoring boggle|word-hunt|wordament]
+//!     [--wrap] [--output <file>]
+//! ```
+
+use std::env;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::process;
+use std::thread;
+
+use rust_game_ai::games::boggle_like::batch::{read_boards, solve_batch, write_csv, write_json};
+use rust_game_ai::games::boggle_like::load_dictionary;
+use rust_game_ai::games::boggle_like::solver::{BoggleSolver, ScoringRules, SolveOptions};
+
+const USAGE: &str = "usage: boggle_batch <boards file> --dictionary <word list> [--format json|csv] \
+    [--threads N] [--min-word-length N] [--scoring boggle|word-hunt|wordament] [--wrap] [--output <file>]";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Debug)]
+struct Args {
+    boards_path: String,
+    dictionary_path: String,
+    format: OutputFormat,
+    threads: usize,
+    options: SolveOptions,
+    output_path: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut boards_path: Option<String> = None;
+    let mut dictionary_path: Option<String> = None;
+    let mut format = OutputFormat::Json;
+    let mut threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let mut options = SolveOptions::default();
+    let mut output_path: Option<String> = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+
+        match arg.as_str() {
+            "--dictionary" => dictionary_path = Some(value("--dictionary")?),
+            "--format" => {
+                format = match value("--format")?.as_str() {
+                    "json" => OutputFormat::Json,
+                    "csv" => OutputFormat::Csv,
+                    other => return Err(format!("unknown format '{}'", other)),
+                }
+            },
+            "--threads" => {
+                threads = value("--threads")?
+                    .parse()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or("--threads must be a positive number")?
+            },
+            "--min-word-length" => {
+                options.min_word_length = value("--min-word-length")?
+                    .parse()
+                    .map_err(|_| "--min-word-length must be a number".to_owned())?
+            },
+            "--scoring" => {
+                let name = value("--scoring")?;
+                options.scoring = ScoringRules::from_name(&name)
+                    .ok_or_else(|| format!("unknown scoring '{}'", name))?
+            },
+            "--wrap" => options.wrap = true,
+            "--output" => output_path = Some(value("--output")?),
+            _ if arg.starts_with("--") => return Err(format!("unknown option '{}'", arg)),
+            _ if boards_path.is_none() => boards_path = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Args {
+        boards_path: boards_path.ok_or("missing boards file")?,
+        dictionary_path: dictionary_path.ok_or("missing --dictionary")?,
+        format,
+        threads,
+        options,
+        output_path,
+    })
+}
+
+fn run(args: Args) -> io::Result<()> {
+    let boards = read_boards(BufReader::new(File::open(&args.boards_path)?))?;
+    let solver = BoggleSolver::new(load_dictionary(&args.dictionary_path)?);
+
+    eprintln!("solving {} boards on {} threads...", boards.len(), args.threads);
+    let summaries = solve_batch(&solver, &boards, &args.options, args.threads);
+
+    let mut writer: Box<dyn Write> = match &args.output_path {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(BufWriter::new(io::stdout())),
+    };
+    match args.format {
+        OutputFormat::Json => write_json(&summaries, &mut writer)?,
+        OutputFormat::Csv => write_csv(&summaries, &mut writer)?,
+    }
+
+    writer.flush()
+}
+
+fn main() {
+    let args = parse_args(env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("{}\n{}", e, USAGE);
+        process::exit(2);
+    });
+
+    if let Err(e) = run(args) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,37 @@
+//! Compiles a word list into the binary format `Dawg::load` reads.
+//!
+//! ```text
+//! build_dawg <word list> <output file>
+//! ```
+
+use std::env;
+use std::process;
+use std::time::Instant;
+
+use rust_game_ai::games::boggle_like::dawg::Dawg;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() != 2 {
+        eprintln!("usage: build_dawg <word list> <output file>");
+        process::exit(2);
+    }
+
+    let start = Instant::now();
+    let result = Dawg::from_word_file(&args[0])
+        .and_then(|dawg| dawg.save(&args[1]).map(|_| dawg));
+
+    match result {
+        Ok(dawg) => println!(
+            "wrote {} nodes and {} edges to {} in {:?}",
+            dawg.node_count(),
+            dawg.edge_count(),
+            args[1],
+            start.elapsed(),
+        ),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        },
+    }
+}
@@ -10,12 +10,12 @@ pub enum D6 {
 impl D6 {
     pub fn value_of(&self) -> u8 {
         match self {
-            One => 1,
-            Two => 2,
-            Three => 3,
-            Four => 4,
-            Five => 5,
-            Six => 6,
+            D6::One => 1,
+            D6::Two => 2,
+            D6::Three => 3,
+            D6::Four => 4,
+            D6::Five => 5,
+            D6::Six => 6,
         }
     }
 }
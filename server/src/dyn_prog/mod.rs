@@ -1,4 +1,3 @@
-pub mod state_value_cache;
 pub mod state_dependency_graph;
 // system dynamics equation
 
@@ -8,29 +7,27 @@ pub mod state_dependency_graph;
 
 use std::collections::VecDeque;
 use std::collections::HashMap;
-use state_value_cache::StateValueCache;
+use crate::value_cache::ValueCache;
 
 
 
-pub fn solve_top_down<S,A>(
+pub fn solve_top_down<S,A,C>(
     x_t: fn(&S, &A) -> S,
     c: fn(&S) -> f64,
     actions_for_state: fn(&S) -> Vec<A>,
-    cache_value: fn(&S, f64) -> (),
-    lookup_value: fn(&S) -> Option<&f64>,
+    value_cache: &mut C,
     x0: S,
-) {
+) where C: ValueCache<S,f64> {
     
 }
 
 
-pub fn solve_bottom_up<S, A>(
+pub fn solve_bottom_up<S,A,C>(
     x_t: fn(&S) -> Vec<S>,
     c: fn(&S) -> f64,
-    cache_value: fn(&S, f64) -> (),
-    lookup_value: fn(&S) -> Option<&f64>,
+    value_cache: &mut C,
     terminal_states: Vec<S>,
-) {
+) where C: ValueCache<S,f64> {
     let mut states_remaining: VecDeque<S> = VecDeque::from(terminal_states);
     let maybe_working_state = states_remaining.pop_front();
 
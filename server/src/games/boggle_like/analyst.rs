@@ -0,0 +1,161 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::load_dictionary;
+use super::solver::{BoggleError, BoggleSolution, BoggleSolver, SolveOptions};
+use super::trie::WordTrie;
+
+#[derive(Debug, PartialEq)]
+pub enum BoggleAnalysisError {
+    NoDictionaries,
+    UnknownDictionary(String),
+    InvalidGrid(BoggleError),
+}
+
+impl fmt::Display for BoggleAnalysisError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoggleAnalysisError::NoDictionaries => {
+                write!(formatter, "no dictionaries are loaded")
+            },
+            BoggleAnalysisError::UnknownDictionary(name) => {
+                write!(formatter, "unknown dictionary '{}'", name)
+            },
+            BoggleAnalysisError::InvalidGrid(e) => {
+                write!(formatter, "invalid grid: {}", e)
+            },
+        }
+    }
+}
+
+/// Solves Boggle grids against a set of named dictionaries.
+#[derive(Debug)]
+pub struct BoggleAnalyst {
+    // sorted by name
+    solvers: Vec<(String, BoggleSolver)>,
+}
+
+impl BoggleAnalyst {
+    pub fn new(dictionaries: Vec<(String, WordTrie)>) -> BoggleAnalyst {
+        let mut solvers: Vec<(String, BoggleSolver)> = dictionaries.into_iter()
+            .map(|(name, dictionary)| (name, BoggleSolver::new(dictionary)))
+            .collect();
+        solvers.sort_by(|a, b| a.0.cmp(&b.0));
+
+        BoggleAnalyst {
+            solvers,
+        }
+    }
+
+    /// Loads every `.txt` word list in `directory`, named after its file
+    /// name without the extension.
+    pub fn load<P>(directory: P) -> io::Result<BoggleAnalyst>
+        where P: AsRef<Path>,
+    {
+        let mut dictionaries: Vec<(String, WordTrie)> = vec![];
+
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|extension| extension != "txt") {
+                continue;
+            }
+
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                println!("loading dictionary '{}'...", name);
+                dictionaries.push((name.to_owned(), load_dictionary(&path)?));
+            }
+        }
+
+        Ok(BoggleAnalyst::new(dictionaries))
+    }
+
+    pub fn dictionary_names(&self) -> Vec<&str> {
+        self.solvers.iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Solves `grid` with the named dictionary, or the first one by name if
+    /// none is given.
+    pub fn solve(
+        &self,
+        grid: &str,
+        dictionary_name: Option<&str>,
+        options: &SolveOptions,
+    ) -> Result<BoggleSolution, BoggleAnalysisError> {
+        let (_, solver) = match dictionary_name {
+            Some(name) => self.solvers.iter()
+                .find(|(solver_name, _)| solver_name == name)
+                .ok_or_else(|| BoggleAnalysisError::UnknownDictionary(name.to_owned()))?,
+            None => self.solvers.first()
+                .ok_or(BoggleAnalysisError::NoDictionaries)?,
+        };
+
+        solver.solve_with_options(grid, options)
+            .map_err(BoggleAnalysisError::InvalidGrid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_analyst() -> BoggleAnalyst {
+        BoggleAnalyst::new(vec![
+            ("small".to_owned(), WordTrie::from_words(&["APP", "LET"])),
+            ("large".to_owned(), WordTrie::from_words(&["APP", "APPLE", "LET", "LETS"])),
+        ])
+    }
+
+    #[test]
+    fn test_solve_with_named_dictionary() {
+        let analyst = create_test_analyst();
+        let options = SolveOptions::default();
+
+        assert_eq!(analyst.dictionary_names(), vec!["large", "small"]);
+
+        let small = analyst.solve("APPL MGGE ZZST DITE", Some("small"), &options).unwrap();
+        assert_eq!(small.total_score, 2);
+
+        // the first dictionary by name is the default
+        let large = analyst.solve("APPL MGGE ZZST DITE", None, &options).unwrap();
+        assert_eq!(large.total_score, 5);
+    }
+
+    #[test]
+    fn test_solve_rejects_bad_input() {
+        let analyst = create_test_analyst();
+        let options = SolveOptions::default();
+
+        assert_eq!(
+            analyst.solve("APPL MGGE ZZST DITE", Some("huge"), &options),
+            Err(BoggleAnalysisError::UnknownDictionary("huge".to_owned())),
+        );
+        assert_eq!(
+            analyst.solve("APPL MGG", None, &options),
+            Err(BoggleAnalysisError::InvalidGrid(BoggleError::RaggedRows)),
+        );
+        assert_eq!(
+            BoggleAnalyst::new(vec![]).solve("APPL MGGE ZZST DITE", None, &options),
+            Err(BoggleAnalysisError::NoDictionaries),
+        );
+    }
+
+    #[test]
+    fn test_load() {
+        let directory = std::env::temp_dir().join("boggle_analyst_test_load");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("words.txt"), "app\nApple\n\nit's\n  let  \n").unwrap();
+        fs::write(directory.join("notes.md"), "not a dictionary").unwrap();
+
+        let analyst = BoggleAnalyst::load(&directory).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(analyst.dictionary_names(), vec!["words"]);
+        let solution = analyst.solve("APPL MGGE ZZST DITE", Some("words"), &SolveOptions::default()).unwrap();
+        let words: Vec<&str> = solution.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, vec!["APPLE", "APP", "LET"]);
+    }
+}
@@ -0,0 +1,237 @@
+use std::io::{self, BufRead, Write};
+use std::thread;
+
+use serde::Serialize;
+
+use super::solver::{BoggleSolver, SolveOptions};
+
+/// One board read from a batch file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchBoard {
+    /// The line of the file the board was on, counting from 1.
+    pub line: usize,
+    pub grid: String,
+}
+
+/// What a board has to offer, or why it couldn't be solved.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BoardSummary {
+    pub line: usize,
+    pub grid: String,
+    pub word_count: usize,
+    pub max_score: u32,
+    pub longest_word: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Reads one board per line in the notation `parse_grid` accepts. Blank
+/// lines and lines starting with `#` are skipped.
+pub fn read_boards<R: BufRead>(reader: R) -> io::Result<Vec<BatchBoard>> {
+    let mut boards: Vec<BatchBoard> = vec![];
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let grid = line.trim();
+        if !grid.is_empty() && !grid.starts_with('#') {
+            boards.push(BatchBoard {
+                line: index + 1,
+                grid: grid.to_owned(),
+            });
+        }
+    }
+
+    Ok(boards)
+}
+
+/// Solves every board against the solver's dictionary, splitting the boards
+/// between `threads` threads. Summaries come back in the order of `boards`.
+pub fn solve_batch(
+    solver: &BoggleSolver,
+    boards: &[BatchBoard],
+    options: &SolveOptions,
+    threads: usize,
+) -> Vec<BoardSummary> {
+    if boards.is_empty() {
+        return vec![];
+    }
+
+    let chunk_size = boards.len().div_ceil(threads.max(1));
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = boards.chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk.iter()
+                        .map(|board| summarize(solver, board, options))
+                        .collect::<Vec<BoardSummary>>()
+                })
+            })
+            .collect();
+
+        workers.into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    })
+}
+
+fn summarize(solver: &BoggleSolver, board: &BatchBoard, options: &SolveOptions) -> BoardSummary {
+    match solver.solve_with_options(&board.grid, options) {
+        Ok(solution) => {
+            // longest first, then alphabetical among words of that length
+            let longest_word = solution.words.iter()
+                .map(|w| &w.word)
+                .min_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)))
+                .cloned();
+
+            BoardSummary {
+                line: board.line,
+                grid: board.grid.clone(),
+                word_count: solution.words.len(),
+                max_score: solution.total_score,
+                longest_word,
+                error: None,
+            }
+        },
+        Err(e) => BoardSummary {
+            line: board.line,
+            grid: board.grid.clone(),
+            word_count: 0,
+            max_score: 0,
+            longest_word: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+pub fn write_json<W: Write>(summaries: &[BoardSummary], writer: &mut W) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *writer, summaries)?;
+    writeln!(writer)
+}
+
+pub fn write_csv<W: Write>(summaries: &[BoardSummary], writer: &mut W) -> io::Result<()> {
+    writeln!(writer, "line,grid,word_count,max_score,longest_word,error")?;
+
+    for summary in summaries {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{}",
+            summary.line,
+            csv_field(&summary.grid),
+            summary.word_count,
+            summary.max_score,
+            csv_field(summary.longest_word.as_deref().unwrap_or("")),
+            csv_field(summary.error.as_deref().unwrap_or("")),
+        )?;
+    }
+
+    Ok(())
+}
+
+// Quotes a field if it holds anything CSV treats specially.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::boggle_like::trie::WordTrie;
+
+    fn create_test_solver() -> BoggleSolver {
+        BoggleSolver::new(WordTrie::from_words(&[
+            "APP", "APPLE", "LET", "LETS", "EGG", "LEG", "GEL", "CAT", "ACT",
+        ]))
+    }
+
+    #[test]
+    fn test_read_boards() {
+        let input = "# tournament round 1\nAPPL MGGE ZZST DITE\n\n  catx xxxx xxxx xxxx  \n";
+
+        let boards = read_boards(input.as_bytes()).unwrap();
+
+        assert_eq!(boards, vec![
+            BatchBoard { line: 2, grid: "APPL MGGE ZZST DITE".to_owned() },
+            BatchBoard { line: 4, grid: "catx xxxx xxxx xxxx".to_owned() },
+        ]);
+    }
+
+    #[test]
+    fn test_solve_batch() {
+        let solver = create_test_solver();
+        let boards: Vec<BatchBoard> = ["APPL MGGE ZZST DITE", "CATX TXXX XXXX XXXX", "AB1"].iter()
+            .enumerate()
+            .map(|(index, grid)| BatchBoard { line: index + 1, grid: grid.to_string() })
+            .collect();
+
+        let summaries = solve_batch(&solver, &boards, &SolveOptions::default(), 2);
+
+        assert_eq!(summaries[0].word_count, 7);
+        assert_eq!(summaries[0].max_score, 8);
+        assert_eq!(summaries[0].longest_word, Some("APPLE".to_owned()));
+        assert_eq!(summaries[1].word_count, 2);
+        // ACT and CAT are the same length
+        assert_eq!(summaries[1].longest_word, Some("ACT".to_owned()));
+        assert_eq!(summaries[2].error, Some("'1' is not a letter".to_owned()));
+
+        // the same answers however the work is split
+        for threads in 1..=4 {
+            assert_eq!(solve_batch(&solver, &boards, &SolveOptions::default(), threads), summaries);
+        }
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let summaries = vec![
+            BoardSummary {
+                line: 1,
+                grid: "CATX XXXX XXXX XXXX".to_owned(),
+                word_count: 2,
+                max_score: 2,
+                longest_word: Some("ACT".to_owned()),
+                error: None,
+            },
+            BoardSummary {
+                line: 2,
+                grid: "A,B".to_owned(),
+                word_count: 0,
+                max_score: 0,
+                longest_word: None,
+                error: Some("row \"A\" is bad".to_owned()),
+            },
+        ];
+
+        let mut output: Vec<u8> = vec![];
+        write_csv(&summaries, &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), [
+            "line,grid,word_count,max_score,longest_word,error",
+            "1,CATX XXXX XXXX XXXX,2,2,ACT,",
+            "2,\"A,B\",0,0,,\"row \"\"A\"\" is bad\"",
+            "",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn test_write_json() {
+        let summaries = vec![BoardSummary {
+            line: 1,
+            grid: "CATX XXXX XXXX XXXX".to_owned(),
+            word_count: 2,
+            max_score: 2,
+            longest_word: Some("ACT".to_owned()),
+            error: None,
+        }];
+
+        let mut output: Vec<u8> = vec![];
+        write_json(&summaries, &mut output).unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+        assert_eq!(parsed[0]["word_count"], 2);
+        assert_eq!(parsed[0]["longest_word"], "ACT");
+        assert!(parsed[0]["error"].is_null());
+    }
+}
@@ -0,0 +1,212 @@
+use super::trie::{TrieCursor, WordTrie};
+use super::{BoggleLikeAnalyst, GridCell, LetterGrid};
+
+/// Finds the same words, in the same order, as `StateNxMAnalyst`, but
+/// keeps a trie cursor for every cell on the current path so each step is
+/// a single child lookup, and tracks the cells in use with a bitmask.
+pub struct CursorAnalyst {
+    valid_words: Vec<Vec<GridCell>>,
+}
+
+impl BoggleLikeAnalyst for CursorAnalyst {
+    fn find_all_valid_words(
+        &mut self,
+        grid: &dyn LetterGrid,
+        dictionary: &WordTrie,
+    ) {
+        let search = GridSearch::new(grid);
+        for cell in grid.grid_cells() {
+            self.search_from(&search, search.index_of(&cell), dictionary);
+        }
+    }
+
+    fn find_valid_words_starting_with_cell(
+        &mut self,
+        cell: &GridCell,
+        grid: &dyn LetterGrid,
+        dictionary: &WordTrie,
+    ) {
+        let search = GridSearch::new(grid);
+        self.search_from(&search, search.index_of(cell), dictionary);
+    }
+}
+
+impl CursorAnalyst {
+    pub fn new() -> CursorAnalyst {
+        CursorAnalyst {
+            valid_words: vec![],
+        }
+    }
+
+    /// The path of every word found so far, in the order they were found.
+    pub fn valid_words(&self) -> &Vec<Vec<GridCell>> {
+        &self.valid_words
+    }
+
+    fn search_from(&mut self, search: &GridSearch, start: usize, dictionary: &WordTrie) {
+        let mut visited = CellSet::new(search.cells.len());
+        let mut path: Vec<usize> = vec![];
+
+        self.extend_path(search, start, dictionary.cursor(), &mut visited, &mut path);
+    }
+
+    fn extend_path(
+        &mut self,
+        search: &GridSearch,
+        cell: usize,
+        cursor: TrieCursor,
+        visited: &mut CellSet,
+        path: &mut Vec<usize>,
+    ) {
+        let cursor = match cursor.step(search.tiles[cell]) {
+            Some(cursor) => cursor,
+            None => return,
+        };
+
+        path.push(cell);
+        visited.insert(cell);
+
+        let outcome = cursor.outcome();
+        if outcome.is_word {
+            self.valid_words.push(path.iter().map(|i| search.cells[*i]).collect());
+        }
+
+        if outcome.has_longer_words {
+            for neighbor in search.neighbors[cell].iter() {
+                if !visited.contains(*neighbor) {
+                    self.extend_path(search, *neighbor, cursor, visited, path);
+                }
+            }
+        }
+
+        visited.remove(cell);
+        path.pop();
+    }
+}
+
+impl Default for CursorAnalyst {
+    fn default() -> Self {
+        CursorAnalyst::new()
+    }
+}
+
+// Everything the search needs about the grid, looked up once by cell index.
+struct GridSearch<'a> {
+    cols: usize,
+    cells: Vec<GridCell>,
+    tiles: Vec<&'a [u8]>,
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl <'a> GridSearch<'a> {
+    fn new(grid: &'a dyn LetterGrid) -> GridSearch<'a> {
+        let (cols, rows) = grid.dimensions();
+        let cells: Vec<GridCell> = (0..cols * rows)
+            .map(|index| GridCell { col: index % cols, row: index / cols })
+            .collect();
+        let tiles = cells.iter()
+            .map(|cell| grid.get_cell_value(cell.col, cell.row))
+            .collect();
+        let neighbors = cells.iter()
+            .map(|cell| {
+                grid.neighbors(cell)
+                    .iter()
+                    .map(|neighbor| neighbor.row * cols + neighbor.col)
+                    .collect()
+            })
+            .collect();
+
+        GridSearch {
+            cols,
+            cells,
+            tiles,
+            neighbors,
+        }
+    }
+
+    fn index_of(&self, cell: &GridCell) -> usize {
+        cell.row * self.cols + cell.col
+    }
+}
+
+struct CellSet {
+    bits: Vec<u64>,
+}
+
+impl CellSet {
+    fn new(cell_count: usize) -> CellSet {
+        CellSet {
+            bits: vec![0; cell_count.div_ceil(64)],
+        }
+    }
+
+    fn insert(&mut self, cell: usize) {
+        self.bits[cell / 64] |= 1 << (cell % 64);
+    }
+
+    fn remove(&mut self, cell: usize) {
+        self.bits[cell / 64] &= !(1 << (cell % 64));
+    }
+
+    fn contains(&self, cell: usize) -> bool {
+        self.bits[cell / 64] & (1 << (cell % 64)) != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::boggle_like::generator::{BoardGenerator, DiceSet};
+    use crate::games::boggle_like::{LetterGridNxM, StateNxMAnalyst};
+
+    // Every three cell path from the first few cells, so the dictionary
+    // has plenty of words and shared prefixes on the board.
+    fn words_on(grid: &LetterGridNxM, start_count: usize) -> Vec<String> {
+        let mut words: Vec<String> = vec![];
+        for cell in grid.grid_cells().iter().take(start_count) {
+            for first in grid.neighbors(cell).iter() {
+                for second in grid.neighbors(first).iter().filter(|c| *c != cell) {
+                    let word: Vec<u8> = [cell, first, second].iter()
+                        .flat_map(|c| grid.get_cell_value(c.col, c.row).to_vec())
+                        .collect();
+                    words.push(String::from_utf8(word).unwrap());
+                }
+            }
+        }
+
+        words
+    }
+
+    #[test]
+    fn test_matches_state_analyst() {
+        let mut generator = BoardGenerator::new(DiceSet::SuperBigBoggle, 3);
+
+        for _ in 0..5 {
+            let grid = generator.roll();
+            let words = words_on(&grid, 6);
+            let word_refs: Vec<&str> = words.iter().map(|w| w.as_str()).collect();
+            let dictionary = WordTrie::from_words(&word_refs);
+
+            let mut expected = StateNxMAnalyst::new();
+            expected.find_all_valid_words(&grid, &dictionary);
+            let mut actual = CursorAnalyst::new();
+            actual.find_all_valid_words(&grid, &dictionary);
+
+            assert!(!actual.valid_words.is_empty());
+            assert_eq!(actual.valid_words, *expected.valid_words());
+        }
+    }
+
+    #[test]
+    fn test_cell_set() {
+        let mut visited = CellSet::new(100);
+
+        visited.insert(3);
+        visited.insert(70);
+        assert!(visited.contains(3) && visited.contains(70));
+        assert!(!visited.contains(4));
+
+        visited.remove(70);
+        assert!(!visited.contains(70));
+    }
+}
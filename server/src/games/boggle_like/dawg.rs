@@ -0,0 +1,443 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use super::read_word_list;
+use super::trie::TrieSearchOutcome;
+
+const MAGIC: &[u8; 4] = b"DAWG";
+const FORMAT_VERSION: u8 = 1;
+const FINAL_BIT: u32 = 1 << 31;
+
+/// A minimized directed acyclic word graph: a trie in which every group of
+/// nodes with the same set of endings is merged into one, so common
+/// suffixes like "-ING" and "-ERS" are stored once.
+///
+/// Nodes are stored flat. Node `n`'s edges are at
+/// `nodes[n] & !FINAL_BIT .. nodes[n + 1] & !FINAL_BIT` in `letters` and
+/// `targets`, sorted by letter, and the high bit of `nodes[n]` says whether
+/// a word ends there. The root is node 0 and the last entry of `nodes` only
+/// marks the end of the edges.
+///
+/// The binary format is the magic bytes `DAWG`, a version byte, the node
+/// and edge counts as little endian `u32`s, then `nodes`, `letters` and
+/// `targets` in that order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dawg {
+    nodes: Vec<u32>,
+    letters: Vec<u8>,
+    targets: Vec<u32>,
+}
+
+/// A position part way down a `Dawg`, used like a `TrieCursor`.
+#[derive(Debug, Clone, Copy)]
+pub struct DawgCursor<'a> {
+    dawg: &'a Dawg,
+    node: u32,
+}
+
+impl <'a> DawgCursor<'a> {
+    /// The cursor after following every one of `letters`, or `None` if no
+    /// word continues that way.
+    pub fn step(&self, letters: &[u8]) -> Option<DawgCursor<'a>> {
+        letters.iter()
+            .try_fold(self.node, |node, letter| self.dawg.child(node, *letter))
+            .map(|node| DawgCursor { dawg: self.dawg, node })
+    }
+
+    pub fn outcome(&self) -> TrieSearchOutcome {
+        let edges = self.dawg.edge_range(self.node);
+
+        TrieSearchOutcome {
+            is_word: self.dawg.nodes[self.node as usize] & FINAL_BIT != 0,
+            has_longer_words: !edges.is_empty(),
+        }
+    }
+}
+
+impl Dawg {
+    pub fn from_words(words: &[&str]) -> Dawg {
+        let mut sorted: Vec<&[u8]> = words.iter().map(|w| w.as_bytes()).collect();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        let mut builder = DawgBuilder::new();
+        for word in sorted.iter().filter(|w| !w.is_empty()) {
+            builder.insert(word);
+        }
+
+        builder.finish()
+    }
+
+    pub fn from_words_owned(words: &[String]) -> Dawg {
+        let word_refs: Vec<&str> = words.iter().map(|w| w.as_str()).collect();
+        Dawg::from_words(&word_refs)
+    }
+
+    /// Builds from a word list the same way `load_dictionary` does.
+    pub fn from_word_file<P>(path: P) -> io::Result<Dawg>
+        where P: AsRef<Path>,
+    {
+        Ok(Dawg::from_words_owned(&read_word_list(path)?))
+    }
+
+    /// Loads a file written by `save`.
+    pub fn load<P>(path: P) -> io::Result<Dawg>
+        where P: AsRef<Path>,
+    {
+        Dawg::from_bytes(&fs::read(path)?)
+    }
+
+    pub fn save<P>(&self, path: P) -> io::Result<()>
+        where P: AsRef<Path>,
+    {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Dawg> {
+        let mut bytes: Vec<u8> = vec![];
+        reader.read_to_end(&mut bytes)?;
+        Dawg::from_bytes(&bytes)
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(
+            13 + self.nodes.len() * 4 + self.letters.len() * 5
+        );
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(FORMAT_VERSION);
+        bytes.extend_from_slice(&(self.nodes.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.letters.len() as u32).to_le_bytes());
+        for node in self.nodes.iter() {
+            bytes.extend_from_slice(&node.to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.letters);
+        for target in self.targets.iter() {
+            bytes.extend_from_slice(&target.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Dawg> {
+        if bytes.len() < 13 || &bytes[0..4] != MAGIC {
+            return Err(invalid_data("not a DAWG file"));
+        }
+        if bytes[4] != FORMAT_VERSION {
+            return Err(invalid_data(&format!("unsupported DAWG version {}", bytes[4])));
+        }
+
+        let node_count = read_u32(&bytes[5..9]) as usize;
+        let edge_count = read_u32(&bytes[9..13]) as usize;
+        let expected_length = node_count.checked_mul(4)
+            .and_then(|length| length.checked_add(edge_count.checked_mul(5)?))
+            .and_then(|length| length.checked_add(13));
+        if expected_length != Some(bytes.len()) {
+            return Err(invalid_data("DAWG file is the wrong length"));
+        }
+        // the root and the sentinel after the last node
+        if node_count < 2 {
+            return Err(invalid_data("DAWG file has no root node"));
+        }
+
+        let letters_start = 13 + node_count * 4;
+        let targets_start = letters_start + edge_count;
+        let dawg = Dawg {
+            nodes: bytes[13..letters_start].chunks(4).map(read_u32).collect(),
+            letters: bytes[letters_start..targets_start].to_vec(),
+            targets: bytes[targets_start..].chunks(4).map(read_u32).collect(),
+        };
+
+        if dawg.is_well_formed() {
+            Ok(dawg)
+        } else {
+            Err(invalid_data("DAWG file has edges out of range"))
+        }
+    }
+
+    /// A cursor at the empty prefix.
+    pub fn cursor(&self) -> DawgCursor<'_> {
+        DawgCursor {
+            dawg: self,
+            node: 0,
+        }
+    }
+
+    /// Same answers as `WordTrie::find`.
+    pub fn find(&self, char_list: &[u8]) -> TrieSearchOutcome {
+        self.cursor()
+            .step(char_list)
+            .map(|cursor| cursor.outcome())
+            .unwrap_or(TrieSearchOutcome {
+                is_word: false,
+                has_longer_words: false,
+            })
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.find(word.as_bytes()).is_word
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.letters.len()
+    }
+
+    fn edge_range(&self, node: u32) -> std::ops::Range<usize> {
+        let node = node as usize;
+        (self.nodes[node] & !FINAL_BIT) as usize..(self.nodes[node + 1] & !FINAL_BIT) as usize
+    }
+
+    fn child(&self, node: u32, letter: u8) -> Option<u32> {
+        let edges = self.edge_range(node);
+        let start = edges.start;
+
+        self.letters[edges]
+            .binary_search(&letter)
+            .ok()
+            .map(|offset| self.targets[start + offset])
+    }
+
+    // Checks the invariants the lookups rely on, so a corrupt file can't
+    // make them index out of bounds.
+    fn is_well_formed(&self) -> bool {
+        let first_edges: Vec<u32> = self.nodes.iter().map(|node| node & !FINAL_BIT).collect();
+        let node_count = self.node_count() as u32;
+
+        first_edges[0] == 0 &&
+            first_edges.windows(2).all(|pair| pair[0] <= pair[1]) &&
+            first_edges[first_edges.len() - 1] as usize == self.letters.len() &&
+            self.targets.iter().all(|target| *target < node_count) &&
+            (0..node_count).all(|node| {
+                self.letters[self.edge_range(node)].windows(2).all(|pair| pair[0] < pair[1])
+            })
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+struct BuildNode {
+    is_final: bool,
+    edges: Vec<(u8, usize)>,
+}
+
+// Builds a minimal DAWG from words given in sorted order (Daciuk et al.).
+// Once a word has been added, the nodes below the prefix it shares with the
+// next word can't change, so they're merged with an equivalent node seen
+// before, if there is one.
+struct DawgBuilder {
+    nodes: Vec<BuildNode>,
+    // nodes already minimized, by their contents
+    register: HashMap<BuildNode, usize>,
+    // (parent, child) for each letter of the last word that isn't minimized yet
+    unchecked: Vec<(usize, usize)>,
+    previous_word: Vec<u8>,
+}
+
+impl DawgBuilder {
+    fn new() -> DawgBuilder {
+        DawgBuilder {
+            nodes: vec![BuildNode::default()],
+            register: HashMap::new(),
+            unchecked: vec![],
+            previous_word: vec![],
+        }
+    }
+
+    fn insert(&mut self, word: &[u8]) {
+        assert!(word > self.previous_word.as_slice(), "words must be inserted in sorted order");
+
+        let common_prefix = word.iter()
+            .zip(self.previous_word.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        self.minimize(common_prefix);
+
+        let mut node = self.unchecked.last().map_or(0, |(_, child)| *child);
+        for letter in word[common_prefix..].iter() {
+            let child = self.nodes.len();
+            self.nodes.push(BuildNode::default());
+            self.nodes[node].edges.push((*letter, child));
+            self.unchecked.push((node, child));
+            node = child;
+        }
+        self.nodes[node].is_final = true;
+
+        self.previous_word = word.to_vec();
+    }
+
+    fn minimize(&mut self, down_to: usize) {
+        while self.unchecked.len() > down_to {
+            let (parent, child) = self.unchecked.pop().unwrap();
+            match self.register.get(&self.nodes[child]) {
+                Some(existing) => self.nodes[parent].edges.last_mut().unwrap().1 = *existing,
+                None => {
+                    self.register.insert(self.nodes[child].clone(), child);
+                },
+            }
+        }
+    }
+
+    // Numbers the nodes still reachable from the root breadth first and lays
+    // them out flat.
+    fn finish(mut self) -> Dawg {
+        self.minimize(0);
+
+        let mut new_ids: HashMap<usize, u32> = HashMap::new();
+        new_ids.insert(0, 0);
+        let mut order: Vec<usize> = vec![0];
+        let mut next = 0;
+        while next < order.len() {
+            for (_, child) in self.nodes[order[next]].edges.iter() {
+                if !new_ids.contains_key(child) {
+                    new_ids.insert(*child, order.len() as u32);
+                    order.push(*child);
+                }
+            }
+            next += 1;
+        }
+
+        let mut dawg = Dawg {
+            nodes: Vec::with_capacity(order.len() + 1),
+            letters: vec![],
+            targets: vec![],
+        };
+        for old_id in order.iter() {
+            let node = &self.nodes[*old_id];
+            let final_bit = if node.is_final { FINAL_BIT } else { 0 };
+            dawg.nodes.push(dawg.letters.len() as u32 | final_bit);
+            for (letter, child) in node.edges.iter() {
+                dawg.letters.push(*letter);
+                dawg.targets.push(new_ids[child]);
+            }
+        }
+        dawg.nodes.push(dawg.letters.len() as u32);
+
+        dawg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::boggle_like::trie::WordTrie;
+
+    const WORDS: [&str; 12] = [
+        "apple", "app", "a", "zeta", "apply", "apt",
+        "tap", "taps", "top", "tops", "tapping", "topping",
+    ];
+
+    #[test]
+    fn test_matches_trie() {
+        let dawg = Dawg::from_words(&WORDS);
+        let trie = WordTrie::from_words(&WORDS);
+
+        let mut queries: Vec<&str> = vec!["", "b", "apps", "tapp", "toppings", "zetas"];
+        for word in WORDS.iter() {
+            queries.extend((1..=word.len()).map(|length| &word[..length]));
+        }
+
+        for query in queries {
+            assert_eq!(dawg.find(query.as_bytes()), trie.find(query.as_bytes()), "{}", query);
+        }
+
+        // stepping a piece at a time gets to the same place
+        let cursor = dawg.cursor().step(b"to").unwrap().step(b"ppin").unwrap().step(b"g").unwrap();
+        assert!(cursor.outcome().is_word);
+        assert!(!cursor.outcome().has_longer_words);
+    }
+
+    #[test]
+    fn test_shares_suffixes() {
+        let dawg = Dawg::from_words(&["tops", "taps", "top", "tap"]);
+
+        // root -T-> 1 -A,O-> 2 -P-> 3 (word) -S-> 4 (word)
+        assert_eq!(dawg.node_count(), 5);
+        assert_eq!(dawg.edge_count(), 5);
+        assert!(dawg.contains("tap") && dawg.contains("tops"));
+        assert!(!dawg.contains("to"));
+    }
+
+    #[test]
+    fn test_duplicates_and_empty_words() {
+        let dawg = Dawg::from_words(&["cat", "", "cat", "ca"]);
+
+        assert_eq!(dawg, Dawg::from_words(&["ca", "cat"]));
+        assert_eq!(dawg.find(b""), TrieSearchOutcome { is_word: false, has_longer_words: true });
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let dawg = Dawg::from_words(&WORDS);
+
+        let mut bytes: Vec<u8> = vec![];
+        dawg.write_to(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 13 + (dawg.node_count() + 1) * 4 + dawg.edge_count() * 5);
+
+        assert_eq!(Dawg::read_from(&mut bytes.as_slice()).unwrap(), dawg);
+
+        let empty = Dawg::from_words(&[]);
+        assert_eq!(Dawg::from_bytes(&empty.to_bytes()).unwrap(), empty);
+        assert!(!empty.find(b"").has_longer_words);
+    }
+
+    #[test]
+    fn test_rejects_bad_files() {
+        let bytes = Dawg::from_words(&WORDS).to_bytes();
+        let error_kind = |bytes: &[u8]| Dawg::from_bytes(bytes).unwrap_err().kind();
+
+        assert_eq!(error_kind(b"TRIE"), io::ErrorKind::InvalidData);
+        assert_eq!(error_kind(&bytes[..bytes.len() - 1]), io::ErrorKind::InvalidData);
+
+        let mut newer_version = bytes.clone();
+        newer_version[4] = FORMAT_VERSION + 1;
+        assert_eq!(error_kind(&newer_version), io::ErrorKind::InvalidData);
+
+        // the last edge points past the last node
+        let mut bad_target = bytes.clone();
+        let length = bad_target.len();
+        bad_target[length - 4..].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(error_kind(&bad_target), io::ErrorKind::InvalidData);
+
+        // a header with only the sentinel node
+        let mut no_root = MAGIC.to_vec();
+        no_root.push(FORMAT_VERSION);
+        no_root.extend_from_slice(&1u32.to_le_bytes());
+        no_root.extend_from_slice(&0u32.to_le_bytes());
+        no_root.extend_from_slice(&0u32.to_le_bytes());
+        assert_eq!(error_kind(&no_root), io::ErrorKind::InvalidData);
+        assert_eq!(error_kind(&no_root[..13]), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let directory = std::env::temp_dir().join("dawg_test_save_and_load");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("words.txt"), "app\nApple\n\nit's\n  let  \n").unwrap();
+
+        let dawg = Dawg::from_word_file(directory.join("words.txt")).unwrap();
+        dawg.save(directory.join("words.dawg")).unwrap();
+        let loaded = Dawg::load(directory.join("words.dawg")).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(loaded, dawg);
+        assert!(loaded.contains("APPLE") && loaded.contains("LET"));
+        assert!(!loaded.contains("IT'S") && !loaded.contains("app"));
+    }
+}
@@ -0,0 +1,247 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use super::LetterGridNxM;
+use super::solver::{BoggleSolution, BoggleSolver, parse_tiles};
+
+/// The dice that come with the different editions of Boggle. Each die is
+/// written as its faces, with multi-letter faces in brackets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiceSet {
+    /// The original 4x4 game.
+    Classic,
+    /// The 4x4 dice used since the late 1980s.
+    NewBoggle,
+    /// 5x5 Big Boggle.
+    BigBoggle,
+    /// 6x6 Super Big Boggle.
+    SuperBigBoggle,
+}
+
+const CLASSIC_DICE: [&str; 16] = [
+    "AACIOT", "ABILTY", "ABJMO[QU]", "ACDEMP",
+    "ACELRS", "ADENVZ", "AHMORS", "BIFORX",
+    "DENOSW", "DKNOTU", "EEFHIY", "EGKLUY",
+    "EGINTV", "EHINPS", "ELPSTU", "GILRUW",
+];
+
+const NEW_BOGGLE_DICE: [&str; 16] = [
+    "AAEEGN", "ABBJOO", "ACHOPS", "AFFKPS",
+    "AOOTTW", "CIMOTU", "DEILRX", "DELRVY",
+    "DISTTY", "EEGHNW", "EEINSU", "EHRTVW",
+    "EIOSST", "ELRTTY", "HIMNU[QU]", "HLNNRZ",
+];
+
+const BIG_BOGGLE_DICE: [&str; 25] = [
+    "AAAFRS", "AAEEEE", "AAFIRS", "ADENNN", "AEEEEM",
+    "AEEGMU", "AEGMNN", "AFIRSY", "BJK[QU]XZ", "CCENST",
+    "CEIILT", "CEILPT", "CEIPST", "DDHNOT", "DHHLOR",
+    "DHLNOR", "DHLNOR", "EIIITT", "EMOTTT", "ENSSSU",
+    "FIPRSY", "GORRVW", "IPRRRY", "NOOTUW", "OOOTTU",
+];
+
+// The EIO die's other three faces are blank, and leave a blocked cell.
+const SUPER_BIG_BOGGLE_DICE: [&str; 36] = [
+    "AAAFRS", "AAEEEE", "AAEEOO", "AAFIRS", "ABDEIO", "ADENNN",
+    "AEEEEM", "AEEGMU", "AEGMNN", "AEILMN", "AEINOU", "AFIRSY",
+    "[AN][ER][HE][IN][QU][TH]", "BBJKXZ", "CCENST", "CDDLNN", "CEIITT", "CEIPST",
+    "CFGNUY", "DDHNOT", "DHHLOR", "DHHNOW", "DHLNOR", "EHILRS",
+    "EIILST", "EILPST", "EIO...", "EMTTTO", "ENSSSU", "GORRVW",
+    "HIRSTV", "HOPRST", "IPRSYY", "JK[QU]WXZ", "NOOTUW", "OOOTTU",
+];
+
+impl DiceSet {
+    /// The number of rows and columns the dice fill.
+    pub fn side(&self) -> usize {
+        match self {
+            DiceSet::Classic | DiceSet::NewBoggle => 4,
+            DiceSet::BigBoggle => 5,
+            DiceSet::SuperBigBoggle => 6,
+        }
+    }
+
+    /// The faces of every die.
+    pub fn dice(&self) -> Vec<Vec<Vec<u8>>> {
+        let dice: &[&str] = match self {
+            DiceSet::Classic => &CLASSIC_DICE,
+            DiceSet::NewBoggle => &NEW_BOGGLE_DICE,
+            DiceSet::BigBoggle => &BIG_BOGGLE_DICE,
+            DiceSet::SuperBigBoggle => &SUPER_BIG_BOGGLE_DICE,
+        };
+
+        dice.iter()
+            .map(|faces| parse_tiles(faces).unwrap())
+            .collect()
+    }
+}
+
+/// What a generated board has to offer before it's accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BoardRequirements {
+    pub min_word_count: Option<usize>,
+    pub min_total_score: Option<u32>,
+}
+
+impl BoardRequirements {
+    pub fn is_met_by(&self, solution: &BoggleSolution) -> bool {
+        self.min_word_count.is_none_or(|count| solution.words.len() >= count) &&
+            self.min_total_score.is_none_or(|score| solution.total_score >= score)
+    }
+}
+
+/// Rolls boards the way the physical game does: the dice are shaken into
+/// random cells and each lands on a random face. The same seed always gives
+/// the same sequence of boards.
+#[derive(Debug)]
+pub struct BoardGenerator {
+    dice_set: DiceSet,
+    dice: Vec<Vec<Vec<u8>>>,
+    rng: StdRng,
+}
+
+impl BoardGenerator {
+    pub fn new(dice_set: DiceSet, seed: u64) -> BoardGenerator {
+        BoardGenerator {
+            dice_set,
+            dice: dice_set.dice(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn dice_set(&self) -> DiceSet {
+        self.dice_set
+    }
+
+    pub fn roll(&mut self) -> LetterGridNxM {
+        let mut order: Vec<usize> = (0..self.dice.len()).collect();
+        order.shuffle(&mut self.rng);
+
+        let tiles: Vec<Vec<u8>> = order.iter()
+            .map(|die| self.dice[*die].choose(&mut self.rng).unwrap().clone())
+            .collect();
+
+        let side = self.dice_set.side();
+        LetterGridNxM::from_tiles(side, side, tiles).expect("dice faces are letters")
+    }
+
+    /// Rolls until a board meets `requirements`, giving up after
+    /// `max_attempts` boards. Returns the board along with its solution.
+    pub fn roll_meeting(
+        &mut self,
+        requirements: &BoardRequirements,
+        solver: &BoggleSolver,
+        max_attempts: usize,
+    ) -> Option<(LetterGridNxM, BoggleSolution)> {
+        for _ in 0..max_attempts {
+            let grid = self.roll();
+            let solution = solver.solve_grid(&grid);
+            if requirements.is_met_by(&solution) {
+                return Some((grid, solution));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::boggle_like::LetterGrid;
+    use crate::games::boggle_like::trie::WordTrie;
+
+    #[test]
+    fn test_dice_sets() {
+        for dice_set in [DiceSet::Classic, DiceSet::NewBoggle, DiceSet::BigBoggle, DiceSet::SuperBigBoggle].iter() {
+            let dice = dice_set.dice();
+
+            assert_eq!(dice.len(), dice_set.side() * dice_set.side());
+            assert!(dice.iter().all(|faces| faces.len() == 6));
+        }
+    }
+
+    // Tries to give `cell` a die showing its tile, moving earlier cells to
+    // other dice if needed.
+    fn assign_die(
+        cell: usize,
+        tiles: &[&[u8]],
+        dice: &[Vec<Vec<u8>>],
+        visited: &mut Vec<bool>,
+        owner: &mut Vec<Option<usize>>,
+    ) -> bool {
+        for die in 0..dice.len() {
+            if visited[die] || !dice[die].iter().any(|face| face.as_slice() == tiles[cell]) {
+                continue;
+            }
+            visited[die] = true;
+
+            let reassigned = match owner[die] {
+                Some(other_cell) => assign_die(other_cell, tiles, dice, visited, owner),
+                None => true,
+            };
+            if reassigned {
+                owner[die] = Some(cell);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    #[test]
+    fn test_roll_uses_every_die_once() {
+        let mut generator = BoardGenerator::new(DiceSet::BigBoggle, 7);
+        let grid = generator.roll();
+        assert_eq!((grid.cols(), grid.rows()), (5, 5));
+
+        // every cell can be matched to a different die showing its face
+        let dice = DiceSet::BigBoggle.dice();
+        let tiles: Vec<&[u8]> = grid.grid_cells()
+            .iter()
+            .map(|cell| grid.get_cell_value(cell.col, cell.row))
+            .collect();
+        let mut owner: Vec<Option<usize>> = vec![None; dice.len()];
+        for cell in 0..tiles.len() {
+            let mut visited = vec![false; dice.len()];
+            assert!(assign_die(cell, &tiles, &dice, &mut visited, &mut owner));
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_boards() {
+        let mut first = BoardGenerator::new(DiceSet::SuperBigBoggle, 42);
+        let mut second = BoardGenerator::new(DiceSet::SuperBigBoggle, 42);
+        let mut other = BoardGenerator::new(DiceSet::SuperBigBoggle, 43);
+
+        let first_boards: Vec<String> = (0..3).map(|_| first.roll().to_string()).collect();
+        let second_boards: Vec<String> = (0..3).map(|_| second.roll().to_string()).collect();
+        let other_boards: Vec<String> = (0..3).map(|_| other.roll().to_string()).collect();
+
+        assert_eq!(first_boards, second_boards);
+        assert_ne!(first_boards, other_boards);
+    }
+
+    #[test]
+    fn test_roll_meeting() {
+        let solver = BoggleSolver::new(WordTrie::from_words(&[
+            "TOE", "TOT", "NOT", "TON", "ONE", "NET", "TEN", "SET", "SOT", "TOO",
+        ]));
+        let requirements = BoardRequirements {
+            min_word_count: Some(3),
+            min_total_score: None,
+        };
+
+        let mut generator = BoardGenerator::new(DiceSet::NewBoggle, 1);
+        let (grid, solution) = generator.roll_meeting(&requirements, &solver, 1000).unwrap();
+
+        assert!(solution.words.len() >= 3);
+        assert_eq!(solver.solve_grid(&grid), solution);
+
+        let impossible = BoardRequirements {
+            min_word_count: None,
+            min_total_score: Some(1000),
+        };
+        assert!(generator.roll_meeting(&impossible, &solver, 10).is_none());
+    }
+}
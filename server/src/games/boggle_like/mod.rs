@@ -1,17 +1,31 @@
 pub mod trie;
 pub mod direction;
+pub mod solver;
+pub mod analyst;
+pub mod generator;
+pub mod cursor_analyst;
+pub mod batch;
+pub mod dawg;
 
 use std::collections::VecDeque;
 use trie::{WordTrie, TrieSearchOutcome};
 use direction::Direction;
+use solver::BoggleError;
 
+use std::fmt;
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
 
-trait LetterGrid {
-    fn get_cell_value(&self, col: usize, row: usize) -> u8;
+pub trait LetterGrid {
+    /// The letters on the tile in a cell - usually one, but faces like "Qu"
+    /// have more.
+    fn get_cell_value(&self, col: usize, row: usize) -> &[u8];
     fn grid_cells(&self) -> Vec<GridCell>;
+    /// The number of columns and rows.
+    fn dimensions(&self) -> (usize, usize);
+    /// Every cell a word can step to from `cell`, clockwise from north.
+    fn neighbors(&self, cell: &GridCell) -> Vec<GridCell>;
     fn neighbor_in_direction(&self, cell: &GridCell, direction: Direction) -> Option<GridCell>;
     fn first_cw_neighbor(
         &self,
@@ -30,63 +44,183 @@ trait LetterGrid {
     fn contains_letter(&self, letter: u8) -> bool;
 }
 
-struct LetterGrid4x4 {
-    grid: [u8; 16],
+/// A grid of tiles with any number of columns and rows, stored row by row.
+///
+/// A tile with no letters is a blocked cell that words can't use, which
+/// gives the grid a shape other than a plain rectangle. A wrapping grid
+/// joins its opposite edges, so every cell has eight neighbors.
+pub struct LetterGridNxM {
+    cols: usize,
+    rows: usize,
+    grid: Vec<Vec<u8>>,
+    wraps: bool,
     letters_present: [bool; 26], // 26 element vec, each element is true if puzzle contains letter, false otherwise
 }
 
-impl LetterGrid4x4 {
-    pub fn new(grid: [u8; 16]) -> LetterGrid4x4 {
+impl LetterGridNxM {
+    /// A grid with a single letter on every tile. Letters may be in either
+    /// case; anything else is an error.
+    pub fn new(cols: usize, rows: usize, grid: Vec<u8>) -> Result<LetterGridNxM, BoggleError> {
+        LetterGridNxM::from_tiles(
+            cols,
+            rows,
+            grid.into_iter().map(|letter| vec![letter]).collect(),
+        )
+    }
+
+    /// A grid with any number of letters on each tile, row by row. Letters
+    /// may be in either case; anything else is an error.
+    pub fn from_tiles(cols: usize, rows: usize, tiles: Vec<Vec<u8>>) -> Result<LetterGridNxM, BoggleError> {
+        if tiles.len() != cols * rows {
+            return Err(BoggleError::WrongTileCount {
+                expected: cols * rows,
+                found: tiles.len(),
+            });
+        }
+        if let Some(letter) = tiles.iter().flatten().find(|letter| !letter.is_ascii_alphabetic()) {
+            return Err(BoggleError::InvalidLetter(*letter as char));
+        }
+
+        let tiles: Vec<Vec<u8>> = tiles.into_iter()
+            .map(|tile| tile.to_ascii_uppercase())
+            .collect();
         let mut letters_present = [false; 26];
-        grid.iter()
+        tiles.iter()
+            .flatten()
             .map(|letter| letter_code_to_alphabet_index(*letter))
             .for_each(|index| letters_present[index] = true);
         
-        LetterGrid4x4 {
-            grid,
+        Ok(LetterGridNxM {
+            cols,
+            rows,
+            grid: tiles,
+            wraps: false,
             letters_present,
+        })
+    }
+
+    /// Turns wrap-around adjacency on or off. Wrapping needs at least three
+    /// rows and columns, or a cell would reach the same neighbor two ways.
+    pub fn with_wrap(mut self, wraps: bool) -> Result<LetterGridNxM, BoggleError> {
+        if wraps && (self.cols < 3 || self.rows < 3) {
+            return Err(BoggleError::TooSmallToWrap);
         }
+
+        self.wraps = wraps;
+        Ok(self)
+    }
+
+    pub fn wraps(&self) -> bool {
+        self.wraps
+    }
+
+    pub fn is_blocked(&self, cell: &GridCell) -> bool {
+        self.get_cell_value(cell.col, cell.row).is_empty()
+    }
+
+    /// Builds a grid from one string per row, e.g. `["APPL", "MGGE", ...]`.
+    pub fn from_rows(rows: &[&str]) -> Result<LetterGridNxM, BoggleError> {
+        if let Some(letter) = rows.iter().flat_map(|row| row.chars()).find(|ch| !ch.is_ascii_alphabetic()) {
+            return Err(BoggleError::InvalidLetter(letter));
+        }
+
+        let cols = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != cols) {
+            return Err(BoggleError::RaggedRows);
+        }
+
+        let grid: Vec<u8> = rows.iter()
+            .flat_map(|row| row.bytes())
+            .collect();
+
+        LetterGridNxM::new(cols, rows.len(), grid)
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
     }
 
-    pub fn to_grid_cell(index: usize) -> GridCell {
+    pub fn to_grid_cell(&self, index: usize) -> GridCell {
         GridCell {
-            col: index % 4,
-            row: index / 4,
+            col: index % self.cols,
+            row: index / self.cols,
         }
     }
 }
 
-impl LetterGrid for LetterGrid4x4 {
+/// Writes the grid the way `solver::parse_grid` reads it: rows separated by
+/// spaces, with multi-letter tiles in brackets and blocked cells as dots.
+impl fmt::Display for LetterGridNxM {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (index, tile) in self.grid.iter().enumerate() {
+            if index > 0 && index % self.cols == 0 {
+                write!(formatter, " ")?;
+            }
+
+            let letters = String::from_utf8_lossy(tile);
+            match tile.len() {
+                0 => write!(formatter, ".")?,
+                1 => write!(formatter, "{}", letters)?,
+                _ => write!(formatter, "[{}]", letters)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl LetterGrid for LetterGridNxM {
     fn contains_letter(&self, letter: u8) -> bool {
         let index = letter_code_to_alphabet_index(letter);
         self.letters_present[index]
     }
 
-    fn get_cell_value(&self, col: usize, row: usize) -> u8 {
-        self.grid[(row * 4) + col]
+    fn get_cell_value(&self, col: usize, row: usize) -> &[u8] {
+        &self.grid[(row * self.cols) + col]
     }
 
     fn grid_cells(&self) -> Vec<GridCell> {
         (0..self.grid.len())
-            .map(|x| LetterGrid4x4::to_grid_cell(x))
+            .map(|x| self.to_grid_cell(x))
+            .filter(|cell| !self.is_blocked(cell))
             .collect::<Vec<GridCell>>()
     }
 
+    fn dimensions(&self) -> (usize, usize) {
+        (self.cols, self.rows)
+    }
+
+    fn neighbors(&self, cell: &GridCell) -> Vec<GridCell> {
+        Direction::list_directions_cw_from_north()
+            .into_iter()
+            .filter_map(|d| self.neighbor_in_direction(cell, d))
+            .collect()
+    }
+
     fn neighbor_in_direction(&self, cell: &GridCell, direction: Direction) -> Option<GridCell> {
         let (d_col, d_row) = Direction::direction_vector(&direction);
         
-        let neighbor_col = cell.col as i8 + d_col;
-        let neighbor_row = cell.row as i8 + d_row;
+        let mut neighbor_col = cell.col as isize + d_col as isize;
+        let mut neighbor_row = cell.row as isize + d_row as isize;
+
+        if self.wraps {
+            neighbor_col = neighbor_col.rem_euclid(self.cols as isize);
+            neighbor_row = neighbor_row.rem_euclid(self.rows as isize);
+        }
 
         if neighbor_col > -1 && 
-           neighbor_col < 4 &&
+           neighbor_col < self.cols as isize &&
            neighbor_row > -1 &&
-           neighbor_row < 4
+           neighbor_row < self.rows as isize
         {
             Some(GridCell {
                 col: neighbor_col as usize,
                 row: neighbor_row as usize,
-            })
+            }).filter(|neighbor| !self.is_blocked(neighbor))
         } else {
             None
         }
@@ -132,45 +266,67 @@ impl LetterGrid for LetterGrid4x4 {
         from: &GridCell,
         to: &GridCell,
     ) -> Option<Direction> {
-        let dx = to.col as i8 - from.col as i8;
-        let dy = to.row as i8 - from.row as i8;
+        let mut dx = to.col as isize - from.col as isize;
+        let mut dy = to.row as isize - from.row as isize;
+
+        // across a wrapped edge the difference is the length of the grid
+        // less one step
+        if self.wraps {
+            dx = unwrap_step(dx, self.cols);
+            dy = unwrap_step(dy, self.rows);
+        }
+
+        if dx.abs() > 1 || dy.abs() > 1 {
+            None
+        } else {
+            Direction::from_direction_vector(&(dx as i8, dy as i8))
+        }
+    }
+}
 
-        Direction::from_direction_vector(&(dx, dy))
+fn unwrap_step(difference: isize, length: usize) -> isize {
+    let length = length as isize;
+    if difference == length - 1 {
+        -1
+    } else if difference == 1 - length {
+        1
+    } else {
+        difference
     }
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub struct GridCell {
-    col: usize,
-    row: usize,
+    pub col: usize,
+    pub row: usize,
 }
 
 pub trait BoggleLikeAnalyst {
     fn find_all_valid_words(
         &mut self,
         grid: &dyn LetterGrid,
-        dictionary: &mut WordTrie,
+        dictionary: &WordTrie,
     );
 
     fn find_valid_words_starting_with_cell(
         &mut self,
         cell: &GridCell,
         grid: &dyn LetterGrid,
-        dictionary: &mut WordTrie,
+        dictionary: &WordTrie,
     );
 
     
 }
 
-pub struct State4x4Analyst {
+pub struct StateNxMAnalyst {
     valid_words: Vec<Vec<GridCell>>,
 }
 
-impl BoggleLikeAnalyst for State4x4Analyst {
+impl BoggleLikeAnalyst for StateNxMAnalyst {
     fn find_all_valid_words(
         &mut self,
         grid: &dyn LetterGrid,
-        dictionary: &mut WordTrie,
+        dictionary: &WordTrie,
     ) {
         for cell in grid.grid_cells() {
             // println!("root cell: {:?}", cell);
@@ -186,7 +342,7 @@ impl BoggleLikeAnalyst for State4x4Analyst {
         &mut self,
         cell: &GridCell,
         grid: &dyn LetterGrid,
-        dictionary: &mut WordTrie,
+        dictionary: &WordTrie,
     ) {
         /*
         while let Some(current_path) = pathfinder.next_path() {
@@ -200,7 +356,7 @@ impl BoggleLikeAnalyst for State4x4Analyst {
         while !path_stack.is_empty() {
             // println!("current path: {:?}", path_stack);
             // println!("current word: {:?}", to_word(&path_stack, grid));
-            let search_outcome = State4x4Analyst::search_for_word_from_path(
+            let search_outcome = StateNxMAnalyst::search_for_word_from_path(
                 &path_stack,
                 grid,
                 dictionary,
@@ -213,7 +369,7 @@ impl BoggleLikeAnalyst for State4x4Analyst {
                 self.collect_word(&path_stack);
             }
 
-            State4x4Analyst::next_path(
+            StateNxMAnalyst::next_path(
                 &mut path_stack,
                 grid,
                 search_outcome.has_longer_words,
@@ -222,20 +378,26 @@ impl BoggleLikeAnalyst for State4x4Analyst {
     }    
 }
 
-impl State4x4Analyst {
-    pub fn new() -> State4x4Analyst {
-        State4x4Analyst {
+impl StateNxMAnalyst {
+    pub fn new() -> StateNxMAnalyst {
+        StateNxMAnalyst {
             valid_words: vec![],
         }
     }
 
+    /// The path of every word found so far, in the order they were found.
+    /// A word that can be traced more than one way shows up once per path.
+    pub fn valid_words(&self) -> &Vec<Vec<GridCell>> {
+        &self.valid_words
+    }
+
     fn search_for_word_from_path(
         path_stack: &Vec<GridCell>,
         letter_grid: &dyn LetterGrid,
-        dictionary: &mut WordTrie,
+        dictionary: &WordTrie,
     ) -> TrieSearchOutcome {
         let word: Vec<u8> = path_stack.iter()
-            .map(|cell| letter_grid.get_cell_value(cell.col, cell.row))
+            .flat_map(|cell| letter_grid.get_cell_value(cell.col, cell.row).iter().copied())
             .collect();
         
         // println!("word for path: {:?}", word);
@@ -259,12 +421,12 @@ impl State4x4Analyst {
         has_longer_words: bool,
     ) {
         if has_longer_words {
-            State4x4Analyst::next_forward_path(
+            StateNxMAnalyst::next_forward_path(
                 path_stack,
                 grid,
             );
         } else {
-            State4x4Analyst::next_backtracked_path(
+            StateNxMAnalyst::next_backtracked_path(
                 path_stack,
                 grid,
             );
@@ -286,7 +448,7 @@ impl State4x4Analyst {
         match maybe_next_cell {
             Some(next_cell) => path_stack.push(next_cell),
             None => {
-                State4x4Analyst::next_backtracked_path(
+                StateNxMAnalyst::next_backtracked_path(
                     path_stack,
                     grid,
                 );
@@ -300,7 +462,7 @@ impl State4x4Analyst {
     ) {
         // println!("looking backward");
         while !path_stack.is_empty() {
-            let found_next_path = State4x4Analyst::backtrack_one(path_stack, grid);
+            let found_next_path = StateNxMAnalyst::backtrack_one(path_stack, grid);
             if found_next_path {
                 break;
             }
@@ -340,7 +502,7 @@ impl State4x4Analyst {
     }
 }
 
-fn dictionary_for_grid(
+pub fn dictionary_for_grid(
     word_list_file_path: &str,
     grid: &dyn LetterGrid,
     min_word_length: usize,
@@ -363,6 +525,32 @@ fn dictionary_for_grid(
     Ok(WordTrie::from_words_owned(all_words.as_slice()))
 }
 
+/// Loads a word list with one word per line. Words are upper cased to match
+/// the grids, and lines that aren't a single word are skipped.
+pub fn load_dictionary<P>(word_list_file_path: P) -> io::Result<WordTrie>
+    where P: AsRef<Path>,
+{
+    let all_words = read_word_list(word_list_file_path)?;
+
+    Ok(WordTrie::from_words_owned(all_words.as_slice()))
+}
+
+/// The words `load_dictionary` would put in its trie, in file order.
+pub fn read_word_list<P>(word_list_file_path: P) -> io::Result<Vec<String>>
+    where P: AsRef<Path>,
+{
+    let mut all_words: Vec<String> = vec![];
+
+    for line in read_lines(word_list_file_path)? {
+        let word = line?.trim().to_uppercase();
+        if !word.is_empty() && word.bytes().all(|b| b.is_ascii_uppercase()) {
+            all_words.push(word);
+        }
+    }
+
+    Ok(all_words)
+}
+
 // The output is wrapped in a Result to allow matching on errors
 // Returns an Iterator to the Reader of the lines of the file.
 fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
@@ -380,7 +568,7 @@ fn letter_code_to_alphabet_index(letter_code: u8) -> usize {
     (letter_code - 65) as usize
 }
 
-fn to_words(
+pub fn to_words(
     paths: &Vec<Vec<GridCell>>,
     grid: &dyn LetterGrid,
 ) -> Vec<String> {
@@ -389,12 +577,12 @@ fn to_words(
         .collect()
 }
 
-fn to_word(
+pub fn to_word(
     cells: &Vec<GridCell>,
     grid: &dyn LetterGrid,
 ) -> String {
     let bytes: Vec<u8> = cells.iter()
-        .map(|cell| grid.get_cell_value(cell.col, cell.row))
+        .flat_map(|cell| grid.get_cell_value(cell.col, cell.row).iter().copied())
         .collect();
     
     String::from_utf8(bytes).unwrap()
@@ -405,7 +593,7 @@ fn to_word(
 mod tests {
     use super::*;
 
-    fn create_test_letter_grid() -> LetterGrid4x4 {
+    fn create_test_letter_grid() -> LetterGridNxM {
         /*
         0  1  2  3
         4  5  6  7
@@ -418,7 +606,7 @@ mod tests {
         Z Z S T
         D I T E
         */
-        LetterGrid4x4::new([
+        LetterGridNxM::new(4, 4, vec![
             letter_code_from_alphabet_index(0), // a
             letter_code_from_alphabet_index(15), // p
             letter_code_from_alphabet_index(15), // p
@@ -435,7 +623,7 @@ mod tests {
             letter_code_from_alphabet_index(8), // i
             letter_code_from_alphabet_index(19), // t
             letter_code_from_alphabet_index(4), // e
-        ])
+        ]).unwrap()
     }
 
     #[test]
@@ -508,7 +696,7 @@ mod tests {
             GridCell { col: 1, row: 0 },
         ];
 
-        State4x4Analyst::next_forward_path(
+        StateNxMAnalyst::next_forward_path(
             &mut path_stack,
             &letter_grid,
         );
@@ -536,7 +724,7 @@ mod tests {
             GridCell { col: 2, row: 1 },
         ];
 
-        State4x4Analyst::next_backtracked_path(
+        StateNxMAnalyst::next_backtracked_path(
             &mut path_stack,
             &letter_grid,
         );
@@ -548,10 +736,10 @@ mod tests {
     fn test_find_all_valid_words() {
         let grid = create_test_letter_grid();
 
-        let grid_as_letters: Vec<String> = grid.grid.iter().map(|b| String::from_utf8(vec![*b]).unwrap()).collect();
+        let grid_as_letters: Vec<String> = grid.grid.iter().map(|tile| String::from_utf8(tile.clone()).unwrap()).collect();
         println!("starting grid {:?}", grid_as_letters);
 
-        let mut dictionary = WordTrie::from_words(&[
+        let dictionary = WordTrie::from_words(&[
             "APP",
             "APPLE",
             "LET",
@@ -564,8 +752,8 @@ mod tests {
             "GEL",
         ]);
         
-        let mut analyst = State4x4Analyst::new();
-        analyst.find_all_valid_words(&grid, &mut dictionary);
+        let mut analyst = StateNxMAnalyst::new();
+        analyst.find_all_valid_words(&grid, &dictionary);
 
         let expected_valid_words: Vec<Vec<GridCell>> = vec![
             vec![0, 1, 2], // app
@@ -578,7 +766,7 @@ mod tests {
         ].into_iter()
             .map(|cells| {
                 cells.into_iter()
-                    .map(|i| LetterGrid4x4::to_grid_cell(i))
+                    .map(|i| grid.to_grid_cell(i))
                     .collect()
             })
             .collect();
@@ -605,10 +793,158 @@ mod tests {
 
         // let mut dictionary = maybe_dictionary.unwrap();
 
-        // let mut analyst = State4x4Analyst::new();
-        // analyst.find_all_valid_words(&grid, &mut dictionary);
+        // let mut analyst = StateNxMAnalyst::new();
+        // analyst.find_all_valid_words(&grid, &dictionary);
 
         // let found_words = to_words(&analyst.valid_words, &grid);
         // println!("found words {:?}", found_words);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_neighbor_in_direction_on_larger_grids() {
+        let big = LetterGridNxM::from_rows(&["ABCDE"; 5]).unwrap();
+        let super_big = LetterGridNxM::from_rows(&["ABCDEF"; 6]).unwrap();
+
+        assert_eq!(big.neighbor_in_direction(&GridCell { col: 3, row: 3 }, Direction::SouthEast), Some(GridCell { col: 4, row: 4 }));
+        assert_eq!(big.neighbor_in_direction(&GridCell { col: 4, row: 4 }, Direction::East), None);
+        assert_eq!(big.neighbor_in_direction(&GridCell { col: 4, row: 4 }, Direction::South), None);
+        assert_eq!(super_big.neighbor_in_direction(&GridCell { col: 4, row: 4 }, Direction::SouthEast), Some(GridCell { col: 5, row: 5 }));
+        assert_eq!(super_big.neighbor_in_direction(&GridCell { col: 5, row: 0 }, Direction::NorthEast), None);
+    }
+
+    #[test]
+    fn test_from_rows_checks_letters() {
+        let grid = LetterGridNxM::from_rows(&["cat", "DOG"]).unwrap();
+        assert_eq!(grid.get_cell_value(0, 0), b"C");
+        assert!(grid.contains_letter(b'T'));
+
+        assert!(matches!(LetterGridNxM::from_rows(&["CAT", "D0G"]), Err(BoggleError::InvalidLetter('0'))));
+        assert!(matches!(LetterGridNxM::from_rows(&["CAT", "DOGS"]), Err(BoggleError::RaggedRows)));
+        assert!(matches!(LetterGridNxM::new(2, 1, vec![b'A', b'{']), Err(BoggleError::InvalidLetter('{'))));
+        assert!(matches!(
+            LetterGridNxM::new(4, 4, vec![b'A'; 15]),
+            Err(BoggleError::WrongTileCount { expected: 16, found: 15 }),
+        ));
+
+        let grid = LetterGridNxM::from_tiles(2, 1, vec![b"qu".to_vec(), b"A".to_vec()]).unwrap();
+        assert_eq!(grid.get_cell_value(0, 0), b"QU");
+        assert!(matches!(
+            LetterGridNxM::from_tiles(2, 1, vec![b"Q@".to_vec(), b"A".to_vec()]),
+            Err(BoggleError::InvalidLetter('@')),
+        ));
+    }
+
+    #[test]
+    fn test_find_all_valid_words_5x5() {
+        let grid = LetterGridNxM::from_rows(&[
+            "ZCATS",
+            "ZZZZT",
+            "ZZZZO",
+            "ZZZZR",
+            "ZZZZE",
+        ]).unwrap();
+        assert_eq!(grid.cols(), 5);
+        assert_eq!(grid.rows(), 5);
+
+        let dictionary = WordTrie::from_words(&[
+            "CAT",
+            "CATS",
+            "STORE",
+        ]);
+
+        let mut analyst = StateNxMAnalyst::new();
+        analyst.find_all_valid_words(&grid, &dictionary);
+
+        assert_eq!(to_words(&analyst.valid_words, &grid), vec!["CAT", "CATS", "STORE"]);
+        assert_eq!(analyst.valid_words[2], vec![
+            GridCell { col: 4, row: 0 },
+            GridCell { col: 4, row: 1 },
+            GridCell { col: 4, row: 2 },
+            GridCell { col: 4, row: 3 },
+            GridCell { col: 4, row: 4 },
+        ]);
+    }
+
+    #[test]
+    fn test_find_all_valid_words_6x6() {
+        let grid = LetterGridNxM::from_rows(&[
+            "SQQQQQ",
+            "QTQQQQ",
+            "QQRQQQ",
+            "QQQAQQ",
+            "QQQQNQ",
+            "QQQQQD",
+        ]).unwrap();
+
+        let dictionary = WordTrie::from_words(&[
+            "STRAND",
+            "RAN",
+            "AND",
+        ]);
+
+        let mut analyst = StateNxMAnalyst::new();
+        analyst.find_all_valid_words(&grid, &dictionary);
+
+        let expected_valid_words: Vec<Vec<GridCell>> = vec![
+            vec![0, 7, 14, 21, 28, 35], // strand
+            vec![14, 21, 28], // ran
+            vec![21, 28, 35], // and
+        ].into_iter()
+            .map(|cells| {
+                cells.into_iter()
+                    .map(|i| grid.to_grid_cell(i))
+                    .collect()
+            })
+            .collect();
+
+        assert_eq!(analyst.valid_words, expected_valid_words);
+    }
+
+    #[test]
+    fn test_blocked_cells() {
+        let grid = LetterGridNxM::from_tiles(3, 2, vec![
+            b"C".to_vec(), vec![], b"T".to_vec(),
+            b"A".to_vec(), b"X".to_vec(), b"X".to_vec(),
+        ]).unwrap();
+
+        assert_eq!(grid.grid_cells().len(), 5);
+        assert_eq!(grid.neighbor_in_direction(&GridCell { col: 0, row: 0 }, Direction::East), None);
+        assert_eq!(grid.to_string(), "C.T AXX");
+
+        // stepping over the blocked cell would spell CT
+        let dictionary = WordTrie::from_words(&["CT", "CAX", "TXA"]);
+        let mut analyst = StateNxMAnalyst::new();
+        analyst.find_all_valid_words(&grid, &dictionary);
+
+        assert_eq!(to_words(&analyst.valid_words, &grid), vec!["CAX", "TXA"]);
+    }
+
+    #[test]
+    fn test_wrapping_grid() {
+        let grid = LetterGridNxM::from_rows(&[
+            "TXXC",
+            "XXXA",
+            "XXXX",
+        ]).unwrap().with_wrap(true).unwrap();
+
+        let corner = GridCell { col: 3, row: 0 };
+        assert_eq!(grid.neighbor_in_direction(&corner, Direction::East), Some(GridCell { col: 0, row: 0 }));
+        assert_eq!(grid.neighbor_in_direction(&corner, Direction::NorthEast), Some(GridCell { col: 0, row: 2 }));
+        assert_eq!(grid.direction_to(&corner, &GridCell { col: 0, row: 2 }), Some(Direction::NorthEast));
+        assert_eq!(grid.direction_to(&GridCell { col: 0, row: 2 }, &corner), Some(Direction::SouthWest));
+
+        // A -> T only touch across the right edge
+        let dictionary = WordTrie::from_words(&["CAT"]);
+        let mut analyst = StateNxMAnalyst::new();
+        analyst.find_all_valid_words(&grid, &dictionary);
+        assert_eq!(to_words(&analyst.valid_words, &grid), vec!["CAT"]);
+
+        let flat = LetterGridNxM::from_rows(&["TXXC", "XXXA", "XXXX"]).unwrap();
+        let mut analyst = StateNxMAnalyst::new();
+        analyst.find_all_valid_words(&flat, &dictionary);
+        assert!(analyst.valid_words.is_empty());
+
+        let small = LetterGridNxM::from_rows(&["TC", "XA"]).unwrap();
+        assert!(matches!(small.with_wrap(true), Err(BoggleError::TooSmallToWrap)));
+    }
+}
@@ -0,0 +1,466 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use super::trie::WordTrie;
+use super::cursor_analyst::CursorAnalyst;
+use super::{BoggleLikeAnalyst, GridCell, LetterGridNxM, to_word};
+
+/// Words shorter than this don't count in Boggle.
+pub const MIN_WORD_LENGTH: usize = 3;
+
+#[derive(Debug, PartialEq)]
+pub enum BoggleError {
+    EmptyGrid,
+    RaggedRows,
+    InvalidLetter(char),
+    /// A bracketed tile that is empty, nested or never closed.
+    InvalidTile(String),
+    TooSmallToWrap,
+    WrongTileCount { expected: usize, found: usize },
+}
+
+impl fmt::Display for BoggleError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoggleError::EmptyGrid => {
+                write!(formatter, "the grid has no letters")
+            },
+            BoggleError::RaggedRows => {
+                write!(formatter, "every row of the grid must have the same length")
+            },
+            BoggleError::InvalidLetter(letter) => {
+                write!(formatter, "'{}' is not a letter", letter)
+            },
+            BoggleError::InvalidTile(row) => {
+                write!(formatter, "row '{}' has a badly bracketed tile", row)
+            },
+            BoggleError::TooSmallToWrap => {
+                write!(formatter, "a grid needs at least three rows and columns to wrap")
+            },
+            BoggleError::WrongTileCount { expected, found } => {
+                write!(formatter, "the grid needs {} tiles but got {}", expected, found)
+            },
+        }
+    }
+}
+
+/// Letter values printed on Wordament tiles.
+pub const WORDAMENT_LETTER_VALUES: [u32; 26] = [
+    2, 5, 3, 3, 1, 5, 4, 4, 2, 10, 6, 3, 4, // A - M
+    2, 2, 4, 8, 2, 2, 2, 4, 6, 6, 9, 5, 8, // N - Z
+];
+
+/// How a word is scored.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ScoringRules {
+    /// Points by length, from 1 for three letters up to 11 for eight or more.
+    #[default]
+    Boggle,
+    /// Word Hunt's points by length, from 100 for three letters.
+    WordHunt,
+    /// The sum of the value of every letter in the word, indexed A to Z.
+    LetterValues([u32; 26]),
+}
+
+impl ScoringRules {
+    pub fn wordament() -> ScoringRules {
+        ScoringRules::LetterValues(WORDAMENT_LETTER_VALUES)
+    }
+
+    /// Looks up rules by name: `boggle`, `word-hunt` or `wordament`.
+    pub fn from_name(name: &str) -> Option<ScoringRules> {
+        match name {
+            "boggle" => Some(ScoringRules::Boggle),
+            "word-hunt" => Some(ScoringRules::WordHunt),
+            "wordament" => Some(ScoringRules::wordament()),
+            _ => None,
+        }
+    }
+
+    /// `word` is upper case.
+    pub fn score(&self, word: &str) -> u32 {
+        match self {
+            ScoringRules::Boggle => word_score(word.len()),
+            ScoringRules::WordHunt => word_hunt_score(word.len()),
+            ScoringRules::LetterValues(values) => {
+                word.bytes()
+                    .map(|letter| values[(letter - b'A') as usize])
+                    .sum()
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolveOptions {
+    pub min_word_length: usize,
+    pub scoring: ScoringRules,
+    /// Whether grids parsed from text wrap around their edges. A
+    /// `LetterGridNxM` built in code says for itself whether it wraps.
+    pub wrap: bool,
+}
+
+impl Default for SolveOptions {
+    fn default() -> Self {
+        SolveOptions {
+            min_word_length: MIN_WORD_LENGTH,
+            scoring: ScoringRules::default(),
+            wrap: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoredWord {
+    pub word: String,
+    pub path: Vec<GridCell>,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoggleSolution {
+    /// Each word once, highest scoring first and alphabetically within a
+    /// score.
+    pub words: Vec<ScoredWord>,
+    pub total_score: u32,
+}
+
+/// Finds every word a dictionary allows on a Boggle board.
+#[derive(Debug)]
+pub struct BoggleSolver {
+    dictionary: WordTrie,
+}
+
+impl BoggleSolver {
+    /// The dictionary's words should be upper case, like the grids.
+    pub fn new(dictionary: WordTrie) -> BoggleSolver {
+        BoggleSolver {
+            dictionary,
+        }
+    }
+
+    /// Solves a grid written the way `parse_grid` reads it, e.g.
+    /// `"APPL MGGE ZZST DITE"` or `"applmggezzstdite"`.
+    pub fn solve(&self, grid: &str) -> Result<BoggleSolution, BoggleError> {
+        self.solve_with_options(grid, &SolveOptions::default())
+    }
+
+    pub fn solve_with_options(
+        &self,
+        grid: &str,
+        options: &SolveOptions,
+    ) -> Result<BoggleSolution, BoggleError> {
+        let grid = parse_grid(grid)?.with_wrap(options.wrap)?;
+
+        Ok(self.solve_grid_with_options(&grid, options))
+    }
+
+    pub fn solve_grid(&self, grid: &LetterGridNxM) -> BoggleSolution {
+        self.solve_grid_with_options(grid, &SolveOptions::default())
+    }
+
+    pub fn solve_grid_with_options(
+        &self,
+        grid: &LetterGridNxM,
+        options: &SolveOptions,
+    ) -> BoggleSolution {
+        let mut analyst = CursorAnalyst::new();
+        analyst.find_all_valid_words(grid, &self.dictionary);
+
+        // the same word can often be traced along several paths - keep the
+        // first one found
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut words: Vec<ScoredWord> = analyst.valid_words()
+            .iter()
+            .filter_map(|path| {
+                // a path can cover more letters than cells when it crosses a
+                // multi-letter tile, and both the minimum and the score go by
+                // letters
+                let word = to_word(path, grid);
+                if word.len() >= options.min_word_length && seen.insert(word.clone()) {
+                    Some(ScoredWord {
+                        score: options.scoring.score(&word),
+                        word,
+                        path: path.clone(),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        words.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.word.cmp(&b.word)));
+        let total_score = words.iter().map(|w| w.score).sum();
+
+        BoggleSolution {
+            words,
+            total_score,
+        }
+    }
+}
+
+/// Word Hunt scoring by word length.
+pub fn word_hunt_score(length: usize) -> u32 {
+    match length {
+        0..=2 => 0,
+        3 => 100,
+        4 => 400,
+        5 => 800,
+        _ => 1400 + 400 * (length as u32 - 6),
+    }
+}
+
+/// Standard Boggle scoring by word length.
+pub fn word_score(length: usize) -> u32 {
+    match length {
+        0..=2 => 0,
+        3 | 4 => 1,
+        5 => 2,
+        6 => 3,
+        7 => 5,
+        _ => 11,
+    }
+}
+
+/// Parses a grid written as its rows separated by spaces, commas or slashes,
+/// or as a single row of tiles for a square grid. A tile with more than one
+/// letter goes in brackets and a blocked cell is a dot: `"[QU]IT. EXAM ..."`.
+pub fn parse_grid(grid: &str) -> Result<LetterGridNxM, BoggleError> {
+    let mut rows: Vec<Vec<Vec<u8>>> = grid
+        .split(|ch: char| ch.is_whitespace() || ch == ',' || ch == '/')
+        .filter(|row| !row.is_empty())
+        .map(parse_tiles)
+        .collect::<Result<Vec<Vec<Vec<u8>>>, BoggleError>>()?;
+
+    if rows.is_empty() {
+        return Err(BoggleError::EmptyGrid);
+    }
+
+    if rows.len() == 1 {
+        rows = split_square(rows.remove(0)).ok_or(BoggleError::RaggedRows)?;
+    }
+
+    let cols = rows[0].len();
+    if rows.iter().any(|row| row.len() != cols) {
+        return Err(BoggleError::RaggedRows);
+    }
+
+    let row_count = rows.len();
+    LetterGridNxM::from_tiles(cols, row_count, rows.into_iter().flatten().collect())
+}
+
+/// Splits one row of a grid into its tiles.
+pub fn parse_tiles(row: &str) -> Result<Vec<Vec<u8>>, BoggleError> {
+    let mut tiles: Vec<Vec<u8>> = vec![];
+    let mut open_tile: Option<Vec<u8>> = None;
+
+    for ch in row.chars() {
+        match (ch, open_tile.as_mut()) {
+            ('[', None) => open_tile = Some(vec![]),
+            (']', Some(tile)) if !tile.is_empty() => tiles.push(open_tile.take().unwrap()),
+            (letter, Some(tile)) if letter.is_ascii_alphabetic() => {
+                tile.push(letter.to_ascii_uppercase() as u8);
+            },
+            (letter, None) if letter.is_ascii_alphabetic() => {
+                tiles.push(vec![letter.to_ascii_uppercase() as u8]);
+            },
+            ('.', None) => tiles.push(vec![]),
+            ('[', Some(_)) | (']', _) => return Err(BoggleError::InvalidTile(row.to_owned())),
+            (other, _) => return Err(BoggleError::InvalidLetter(other)),
+        }
+    }
+
+    if open_tile.is_some() {
+        return Err(BoggleError::InvalidTile(row.to_owned()));
+    }
+
+    Ok(tiles)
+}
+
+// A single row is only a grid if it makes a square.
+fn split_square(tiles: Vec<Vec<u8>>) -> Option<Vec<Vec<Vec<u8>>>> {
+    let side = (1..=tiles.len()).find(|n| n * n >= tiles.len())?;
+    if side * side != tiles.len() {
+        return None;
+    }
+
+    Some(
+        tiles.chunks(side)
+            .map(|row| row.to_vec())
+            .collect()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::boggle_like::LetterGrid;
+
+    fn create_test_solver() -> BoggleSolver {
+        BoggleSolver::new(WordTrie::from_words(&[
+            "APP",
+            "APPLE",
+            "LET",
+            "EGG",
+            "LEG",
+            "DID",
+            "DAZE",
+            "TIDE",
+            "LETS",
+            "GEL",
+            "GELS",
+            "STEGS",
+        ]))
+    }
+
+    #[test]
+    fn test_solve() {
+        let solver = create_test_solver();
+
+        let solution = solver.solve("APPL MGGE ZZST DITE").unwrap();
+
+        let words: Vec<(&str, u32)> = solution.words.iter()
+            .map(|w| (w.word.as_str(), w.score))
+            .collect();
+        assert_eq!(words, vec![
+            ("APPLE", 2),
+            ("APP", 1),
+            ("EGG", 1),
+            ("GEL", 1),
+            ("LEG", 1),
+            ("LET", 1),
+            ("LETS", 1),
+        ]);
+        assert_eq!(solution.total_score, 8);
+        assert_eq!(solution.words[0].path, vec![
+            GridCell { col: 0, row: 0 },
+            GridCell { col: 1, row: 0 },
+            GridCell { col: 2, row: 0 },
+            GridCell { col: 3, row: 0 },
+            GridCell { col: 3, row: 1 },
+        ]);
+    }
+
+    #[test]
+    fn test_words_are_deduplicated() {
+        // EGG can be traced from either E
+        let solver = BoggleSolver::new(WordTrie::from_words(&["EGG"]));
+
+        let solution = solver.solve("EGGE").unwrap();
+
+        assert_eq!(solution.words.len(), 1);
+        assert_eq!(solution.total_score, 1);
+    }
+
+    #[test]
+    fn test_min_word_length() {
+        let solver = create_test_solver();
+        let options = SolveOptions { min_word_length: 4, ..SolveOptions::default() };
+
+        let solution = solver.solve_with_options("APPL MGGE ZZST DITE", &options).unwrap();
+
+        let words: Vec<&str> = solution.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, vec!["APPLE", "LETS"]);
+        assert_eq!(solution.total_score, 3);
+    }
+
+    #[test]
+    fn test_multi_letter_tiles() {
+        let solver = BoggleSolver::new(WordTrie::from_words(&[
+            "QUIT",
+            "QUITE",
+            "QIT",
+            "THE",
+            "TIE",
+        ]));
+
+        let solution = solver.solve("[Qu]IX TE[TH] XXX").unwrap();
+
+        let words: Vec<(&str, usize, u32)> = solution.words.iter()
+            .map(|w| (w.word.as_str(), w.path.len(), w.score))
+            .collect();
+        // a Q on its own isn't on the board, and THE is spelled with the TH
+        // tile rather than the loose T
+        assert_eq!(words, vec![
+            ("QUITE", 4, 2),
+            ("QUIT", 3, 1),
+            ("THE", 2, 1),
+            ("TIE", 3, 1),
+        ]);
+    }
+
+    #[test]
+    fn test_scoring_rules() {
+        let solver = create_test_solver();
+        let grid = "APPL MGGE ZZST DITE";
+
+        let word_hunt = SolveOptions { scoring: ScoringRules::WordHunt, ..SolveOptions::default() };
+        let solution = solver.solve_with_options(grid, &word_hunt).unwrap();
+        assert_eq!(solution.words[0].word, "APPLE");
+        assert_eq!(solution.words[0].score, 800);
+        assert_eq!(solution.total_score, 800 + 400 + 5 * 100);
+
+        // the longer LETS (3 + 1 + 2 + 2) is worth no more than LEG (3 + 1 + 4)
+        let wordament = SolveOptions { scoring: ScoringRules::wordament(), ..SolveOptions::default() };
+        let solution = solver.solve_with_options(grid, &wordament).unwrap();
+        let words: Vec<(&str, u32)> = solution.words.iter()
+            .map(|w| (w.word.as_str(), w.score))
+            .collect();
+        assert_eq!(words, vec![
+            ("APPLE", 14),
+            ("APP", 10),
+            ("EGG", 9),
+            ("GEL", 8),
+            ("LEG", 8),
+            ("LETS", 8),
+            ("LET", 6),
+        ]);
+    }
+
+    #[test]
+    fn test_wrap_option() {
+        let solver = BoggleSolver::new(WordTrie::from_words(&["CAT"]));
+        let wrapping = SolveOptions { wrap: true, ..SolveOptions::default() };
+
+        assert_eq!(solver.solve("TXXC XXXA XXXX").unwrap().words.len(), 0);
+        assert_eq!(solver.solve_with_options("TXXC XXXA XXXX", &wrapping).unwrap().words.len(), 1);
+        assert_eq!(solver.solve_with_options("TC XA", &wrapping), Err(BoggleError::TooSmallToWrap));
+    }
+
+    #[test]
+    fn test_word_score() {
+        let scores: Vec<u32> = (2..=9).map(word_score).collect();
+        assert_eq!(scores, vec![0, 1, 1, 2, 3, 5, 11, 11]);
+
+        let scores: Vec<u32> = (2..=9).map(word_hunt_score).collect();
+        assert_eq!(scores, vec![0, 100, 400, 800, 1400, 1800, 2200, 2600]);
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let grid = parse_grid("applmggezzstdite").unwrap();
+        assert_eq!((grid.cols(), grid.rows()), (4, 4));
+
+        let grid = parse_grid("ABCDE/FGHIJ/KLMNO/PQRST/UVWXY").unwrap();
+        assert_eq!((grid.cols(), grid.rows()), (5, 5));
+
+        assert_eq!(parse_grid("  ").err(), Some(BoggleError::EmptyGrid));
+        assert_eq!(parse_grid("ABC DE").err(), Some(BoggleError::RaggedRows));
+        assert_eq!(parse_grid("ABCDE").err(), Some(BoggleError::RaggedRows));
+        assert_eq!(parse_grid("AB1D").err(), Some(BoggleError::InvalidLetter('1')));
+
+        let grid = parse_grid("[qu]BC[TH]").unwrap();
+        assert_eq!((grid.cols(), grid.rows()), (2, 2));
+        assert_eq!(grid.get_cell_value(0, 0), b"QU");
+        assert_eq!(grid.get_cell_value(1, 1), b"TH");
+
+        let grid = parse_grid("AB. .CD").unwrap();
+        assert_eq!((grid.cols(), grid.rows()), (3, 2));
+        assert!(grid.get_cell_value(2, 0).is_empty());
+        assert_eq!(grid.to_string(), "AB. .CD");
+
+        assert_eq!(parse_grid("[QU").err(), Some(BoggleError::InvalidTile("[QU".to_owned())));
+        assert_eq!(parse_grid("A[]BC").err(), Some(BoggleError::InvalidTile("A[]BC".to_owned())));
+        assert_eq!(parse_grid("A]BC").err(), Some(BoggleError::InvalidTile("A]BC".to_owned())));
+    }
+}
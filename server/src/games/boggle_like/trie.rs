@@ -1,9 +1,37 @@
 use std::collections::HashMap;
 
+/// What's known about a word besides its spelling.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WordMetadata {
+    /// Where the word ranks by how often it's used, 1 being the most common.
+    pub frequency_rank: Option<u32>,
+    /// The word list the word came from.
+    pub source: Option<String>,
+    pub offensive: bool,
+    pub obscure: bool,
+}
+
+impl WordMetadata {
+    /// True if the word is among the `max_rank` most common words.
+    pub fn is_common(&self, max_rank: u32) -> bool {
+        self.frequency_rank.is_some_and(|rank| rank <= max_rank)
+    }
+}
+
+// what words inserted without metadata report
+static NO_METADATA: WordMetadata = WordMetadata {
+    frequency_rank: None,
+    source: None,
+    offensive: false,
+    obscure: false,
+};
+
 #[derive(Debug)]
 pub struct TrieNode {
     value: Option<u8>,
     is_final: bool,
+    // boxed, as most words in a big list won't have any
+    metadata: Option<Box<WordMetadata>>,
     child_nodes: HashMap<u8, TrieNode>,
 }
 
@@ -12,6 +40,7 @@ impl TrieNode {
         TrieNode {
             value: Some(ch),
             is_final,
+            metadata: None,
             child_nodes: HashMap::new(),
         }
     }
@@ -20,6 +49,7 @@ impl TrieNode {
         TrieNode {
             value: Option::None,
             is_final: false,
+            metadata: None,
             child_nodes: HashMap::new(),
         }
     }
@@ -31,6 +61,57 @@ impl TrieNode {
     pub fn insert_value(&mut self, ch: u8, is_final: bool) {
         self.child_nodes.insert(ch, TrieNode::new(ch, is_final));
     }
+
+    pub fn child(&self, ch: u8) -> Option<&TrieNode> {
+        self.child_nodes.get(&ch)
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.is_final
+    }
+
+    pub fn has_children(&self) -> bool {
+        !self.child_nodes.is_empty()
+    }
+
+    /// The metadata of the word ending here, if one does.
+    pub fn metadata(&self) -> Option<&WordMetadata> {
+        if self.is_final {
+            Some(self.metadata.as_deref().unwrap_or(&NO_METADATA))
+        } else {
+            None
+        }
+    }
+}
+
+/// A position part way down a `WordTrie`. Searches that grow a word one
+/// piece at a time can keep a cursor for each prefix and step it forward,
+/// rather than looking up the whole word from the root every time.
+#[derive(Debug, Clone, Copy)]
+pub struct TrieCursor<'a> {
+    node: &'a TrieNode,
+}
+
+impl <'a> TrieCursor<'a> {
+    /// The cursor after following every one of `letters`, or `None` if no
+    /// word continues that way.
+    pub fn step(&self, letters: &[u8]) -> Option<TrieCursor<'a>> {
+        letters.iter()
+            .try_fold(self.node, |node, letter| node.child(*letter))
+            .map(|node| TrieCursor { node })
+    }
+
+    pub fn outcome(&self) -> TrieSearchOutcome {
+        TrieSearchOutcome {
+            is_word: self.node.is_final(),
+            has_longer_words: self.node.has_children(),
+        }
+    }
+
+    /// The metadata of the word the cursor is at, if it's at one.
+    pub fn metadata(&self) -> Option<&'a WordMetadata> {
+        self.node.metadata()
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -71,6 +152,13 @@ impl WordTrie {
         trie
     }
 
+    /// A cursor at the empty prefix.
+    pub fn cursor(&self) -> TrieCursor<'_> {
+        TrieCursor {
+            node: &self.root_node,
+        }
+    }
+
     // Insert a string
     pub fn insert(&mut self, word: &str) {
         let mut current_node = &mut self.root_node;
@@ -112,36 +200,252 @@ impl WordTrie {
         }
     }
 
+    /// Inserts `word`, replacing any metadata it already had.
+    pub fn insert_with_metadata(&mut self, word: &str, metadata: WordMetadata) {
+        self.insert(word);
+        self.set_metadata(word, metadata);
+    }
+
+    /// Sets the metadata of a word already in the trie. Returns false if
+    /// `word` isn't in it.
+    pub fn set_metadata(&mut self, word: &str, metadata: WordMetadata) -> bool {
+        let mut node = &mut self.root_node;
+        for letter in word.as_bytes() {
+            node = match node.child_nodes.get_mut(letter) {
+                Some(child) => child,
+                None => return false,
+            };
+        }
+
+        if node.is_final {
+            node.metadata = Some(Box::new(metadata));
+        }
+        node.is_final
+    }
+
+    /// The metadata of `word`, or `None` if it isn't in the trie. Words
+    /// inserted without any have the default metadata.
+    pub fn metadata(&self, word: &str) -> Option<&WordMetadata> {
+        self.cursor()
+            .step(word.as_bytes())
+            .and_then(|cursor| cursor.metadata())
+    }
+
+    /// Removes `word`, along with any nodes left leading to no word.
+    /// Returns false if it wasn't in the trie.
+    pub fn remove(&mut self, word: &str) -> bool {
+        remove_word(&mut self.root_node, word.as_bytes())
+    }
+
+    /// Removes every word `keep` returns false for, pruning as `remove` does.
+    pub fn retain<F>(&mut self, mut keep: F)
+        where F: FnMut(&str, &WordMetadata) -> bool,
+    {
+        retain_words(&mut self.root_node, &mut vec![], &mut keep);
+    }
+
     // Find a string
-    pub fn find(&mut self, char_list: &[u8]) -> TrieSearchOutcome {
-        let mut current_node = &mut self.root_node;
-        let mut all_chars_processed = true;
+    pub fn find(&self, char_list: &[u8]) -> TrieSearchOutcome {
+        self.cursor()
+            .step(char_list)
+            .map(|cursor| cursor.outcome())
+            .unwrap_or(TrieSearchOutcome {
+                is_word: false,
+                has_longer_words: false,
+            })
+    }
 
-        for counter in 0..char_list.len() {
-            if !current_node.child_nodes.contains_key(&char_list[counter]) {
-                all_chars_processed = false;
-                break;
+    /// Every word starting with `prefix`, including `prefix` itself if it's
+    /// a word, in alphabetical order.
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut words: Vec<String> = vec![];
+
+        if let Some(cursor) = self.cursor().step(prefix.as_bytes()) {
+            let mut word = prefix.as_bytes().to_vec();
+            collect_words(cursor.node, &mut word, &mut words);
+        }
+
+        words.sort();
+        words
+    }
+
+    /// Every word as long as `pattern` with the same letters in the same
+    /// places, where a `?` in the pattern matches any letter. Alphabetical.
+    pub fn matching(&self, pattern: &str) -> Vec<String> {
+        let mut words: Vec<String> = vec![];
+        collect_matches(&self.root_node, pattern.as_bytes(), &mut vec![], &mut words);
+
+        words.sort();
+        words
+    }
+
+    /// Every word that uses all of the tiles in `rack`, where a `?` is a
+    /// blank that can stand for any letter. Alphabetical.
+    pub fn anagrams(&self, rack: &str) -> Vec<String> {
+        let mut words: Vec<String> = vec![];
+        let mut rack = Rack::new(rack);
+        collect_anagrams(&self.root_node, &mut rack, true, &mut vec![], &mut words);
+
+        words.sort();
+        words
+    }
+
+    /// Every word that can be made from some of the tiles in `rack`, with
+    /// blanks written as `?`. Longest first, then alphabetical.
+    pub fn subanagrams(&self, rack: &str) -> Vec<String> {
+        let mut words: Vec<String> = vec![];
+        let mut rack = Rack::new(rack);
+        collect_anagrams(&self.root_node, &mut rack, false, &mut vec![], &mut words);
+
+        words.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        words
+    }
+}
+
+const BLANK: u8 = b'?';
+
+// The tiles left to spell with.
+struct Rack {
+    counts: [usize; 256],
+    blanks: usize,
+    tiles: usize,
+}
+
+impl Rack {
+    fn new(tiles: &str) -> Rack {
+        let mut rack = Rack {
+            counts: [0; 256],
+            blanks: 0,
+            tiles: tiles.len(),
+        };
+        for tile in tiles.bytes() {
+            if tile == BLANK {
+                rack.blanks += 1;
             } else {
-                current_node = current_node
-                    .child_nodes
-                    .get_mut(&char_list[counter])
-                    .unwrap();
+                rack.counts[tile as usize] += 1;
             }
         }
 
-        if all_chars_processed {
-            TrieSearchOutcome {
-                is_word: current_node.is_final,
-                has_longer_words: !current_node.child_nodes.is_empty(),
-            }
-        } else {
-            TrieSearchOutcome {
-                is_word: false,
-                has_longer_words: false
+        rack
+    }
+}
+
+fn remove_word(node: &mut TrieNode, word: &[u8]) -> bool {
+    let (letter, rest) = match word.split_first() {
+        Some(split) => split,
+        None => {
+            let removed = node.is_final;
+            node.is_final = false;
+            node.metadata = None;
+            return removed;
+        },
+    };
+
+    let child = match node.child_nodes.get_mut(letter) {
+        Some(child) => child,
+        None => return false,
+    };
+    let removed = remove_word(child, rest);
+    if removed && !child.is_final && child.child_nodes.is_empty() {
+        node.child_nodes.remove(letter);
+    }
+
+    removed
+}
+
+fn retain_words<F>(node: &mut TrieNode, word: &mut Vec<u8>, keep: &mut F)
+    where F: FnMut(&str, &WordMetadata) -> bool,
+{
+    if node.is_final {
+        let metadata = node.metadata.as_deref().unwrap_or(&NO_METADATA);
+        if !keep(std::str::from_utf8(word).unwrap(), metadata) {
+            node.is_final = false;
+            node.metadata = None;
+        }
+    }
+
+    for (letter, child) in node.child_nodes.iter_mut() {
+        word.push(*letter);
+        retain_words(child, word, keep);
+        word.pop();
+    }
+    node.child_nodes.retain(|_, child| child.is_final || !child.child_nodes.is_empty());
+}
+
+// Words only ever hold whole inserted strings, so they're valid UTF-8.
+fn push_word(word: &[u8], words: &mut Vec<String>) {
+    words.push(String::from_utf8(word.to_vec()).unwrap());
+}
+
+fn collect_words(node: &TrieNode, word: &mut Vec<u8>, words: &mut Vec<String>) {
+    if node.is_final {
+        push_word(word, words);
+    }
+
+    for (letter, child) in node.child_nodes.iter() {
+        word.push(*letter);
+        collect_words(child, word, words);
+        word.pop();
+    }
+}
+
+fn collect_matches(node: &TrieNode, pattern: &[u8], word: &mut Vec<u8>, words: &mut Vec<String>) {
+    let (next, rest) = match pattern.split_first() {
+        Some(split) => split,
+        None => {
+            if node.is_final {
+                push_word(word, words);
             }
+            return;
+        },
+    };
+
+    for (letter, child) in node.child_nodes.iter() {
+        if *next == BLANK || next == letter {
+            word.push(*letter);
+            collect_matches(child, rest, word, words);
+            word.pop();
         }
     }
+}
 
+fn collect_anagrams(
+    node: &TrieNode,
+    rack: &mut Rack,
+    use_all_tiles: bool,
+    word: &mut Vec<u8>,
+    words: &mut Vec<String>,
+) {
+    if node.is_final && !word.is_empty() && (!use_all_tiles || rack.tiles == 0) {
+        push_word(word, words);
+    }
+
+    for (letter, child) in node.child_nodes.iter() {
+        let letter_index = *letter as usize;
+        // playing the letter itself always leaves a better rack than
+        // playing a blank for it, and finds the same words
+        let used_blank = if rack.counts[letter_index] > 0 {
+            rack.counts[letter_index] -= 1;
+            false
+        } else if rack.blanks > 0 {
+            rack.blanks -= 1;
+            true
+        } else {
+            continue;
+        };
+        rack.tiles -= 1;
+
+        word.push(*letter);
+        collect_anagrams(child, rack, use_all_tiles, word, words);
+        word.pop();
+
+        rack.tiles += 1;
+        if used_blank {
+            rack.blanks += 1;
+        } else {
+            rack.counts[letter_index] += 1;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -159,7 +463,7 @@ mod tests {
             "apt",
         ];
 
-        let mut trie = WordTrie::from_words(&words);
+        let trie = WordTrie::from_words(&words);
 
         assert_eq!(
             trie.find("a".as_bytes()),
@@ -209,4 +513,139 @@ mod tests {
             },
         )
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_cursor_matches_find() {
+        let trie = WordTrie::from_words(&["apple", "app", "a", "zeta", "apply", "apt"]);
+
+        for word in ["a", "ap", "app", "apple", "apps", "ba", "zeta", "zet"].iter() {
+            let outcome = trie.cursor()
+                .step(word.as_bytes())
+                .map(|cursor| cursor.outcome())
+                .unwrap_or(TrieSearchOutcome { is_word: false, has_longer_words: false });
+
+            assert_eq!(outcome, trie.find(word.as_bytes()), "{}", word);
+        }
+
+        // stepping a piece at a time gets to the same place
+        let cursor = trie.cursor().step(b"ap").unwrap().step(b"pl").unwrap().step(b"e").unwrap();
+        assert!(cursor.outcome().is_word);
+    }
+
+    #[test]
+    fn test_words_with_prefix() {
+        let trie = WordTrie::from_words(&["apple", "app", "a", "zeta", "apply", "apt"]);
+
+        assert_eq!(trie.words_with_prefix("ap"), vec!["app", "apple", "apply", "apt"]);
+        assert_eq!(trie.words_with_prefix("app"), vec!["app", "apple", "apply"]);
+        assert_eq!(trie.words_with_prefix("").len(), 6);
+        assert!(trie.words_with_prefix("b").is_empty());
+    }
+
+    #[test]
+    fn test_matching() {
+        let trie = WordTrie::from_words(&["cat", "cot", "cut", "coat", "act", "cats"]);
+
+        assert_eq!(trie.matching("c?t"), vec!["cat", "cot", "cut"]);
+        assert_eq!(trie.matching("???t"), vec!["coat"]);
+        assert_eq!(trie.matching("?a??"), vec!["cats"]);
+        assert_eq!(trie.matching("act"), vec!["act"]);
+        assert!(trie.matching("c?").is_empty());
+    }
+
+    #[test]
+    fn test_anagrams() {
+        let trie = WordTrie::from_words(&[
+            "STOP", "POTS", "TOPS", "SPOT", "POST", "OPTS", "TOP", "POT", "SO", "TOO", "STOPS",
+        ]);
+
+        assert_eq!(trie.anagrams("OPST"), vec!["OPTS", "POST", "POTS", "SPOT", "STOP", "TOPS"]);
+        // one O only
+        assert!(trie.anagrams("OOT").contains(&"TOO".to_owned()));
+        assert!(trie.anagrams("OPT").iter().all(|w| w != "TOO"));
+        // the blank stands in for the second S
+        assert_eq!(trie.anagrams("STOP?"), vec!["STOPS"]);
+    }
+
+    #[test]
+    fn test_subanagrams() {
+        let trie = WordTrie::from_words(&["TOP", "POT", "SO", "TOO", "STOP", "ZOO"]);
+
+        assert_eq!(trie.subanagrams("TOPS"), vec!["STOP", "POT", "TOP", "SO"]);
+        // each blank covers one missing letter, and words can't use more
+        // tiles than the rack has
+        assert_eq!(trie.subanagrams("O?"), vec!["SO"]);
+        assert_eq!(trie.subanagrams("O??"), vec!["POT", "TOO", "TOP", "ZOO", "SO"]);
+        assert!(trie.subanagrams("").is_empty());
+    }
+
+    #[test]
+    fn test_remove_prunes_branches() {
+        let mut trie = WordTrie::from_words(&["app", "apple", "apt", "zeta"]);
+
+        assert!(trie.remove("apple"));
+        assert_eq!(trie.find(b"appl"), TrieSearchOutcome { is_word: false, has_longer_words: false });
+        assert_eq!(trie.find(b"app"), TrieSearchOutcome { is_word: true, has_longer_words: false });
+
+        // a prefix of other words stays as a path, but not as a word
+        trie.insert("apple");
+        assert!(trie.remove("app"));
+        assert_eq!(trie.find(b"app"), TrieSearchOutcome { is_word: false, has_longer_words: true });
+
+        assert!(!trie.remove("app"));
+        assert!(!trie.remove("zet"));
+        assert!(!trie.remove("zetas"));
+
+        assert!(trie.remove("zeta"));
+        assert!(!trie.find(b"z").has_longer_words);
+        assert_eq!(trie.words_with_prefix(""), vec!["apple", "apt"]);
+    }
+
+    #[test]
+    fn test_metadata() {
+        let mut trie = WordTrie::from_words(&["cat", "cats"]);
+        let common = WordMetadata {
+            frequency_rank: Some(120),
+            source: Some("enable".to_owned()),
+            ..WordMetadata::default()
+        };
+
+        trie.insert_with_metadata("dog", common.clone());
+
+        assert_eq!(trie.metadata("dog"), Some(&common));
+        assert_eq!(trie.metadata("cat"), Some(&WordMetadata::default()));
+        assert_eq!(trie.metadata("ca"), None);
+        assert_eq!(trie.cursor().step(b"do").unwrap().step(b"g").unwrap().metadata(), Some(&common));
+        assert!(common.is_common(1000) && !common.is_common(100));
+
+        assert!(trie.set_metadata("cat", WordMetadata { obscure: true, ..WordMetadata::default() }));
+        assert!(!trie.set_metadata("ca", WordMetadata::default()));
+        assert!(trie.metadata("cat").unwrap().obscure);
+
+        // removing a word drops its metadata
+        trie.remove("dog");
+        trie.insert("dog");
+        assert_eq!(trie.metadata("dog"), Some(&WordMetadata::default()));
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut trie = WordTrie::new();
+        trie.insert_with_metadata("the", WordMetadata { frequency_rank: Some(1), ..WordMetadata::default() });
+        trie.insert_with_metadata("then", WordMetadata { frequency_rank: Some(90), ..WordMetadata::default() });
+        trie.insert_with_metadata("thew", WordMetadata { obscure: true, ..WordMetadata::default() });
+        trie.insert_with_metadata("theca", WordMetadata { obscure: true, ..WordMetadata::default() });
+        trie.insert("thee");
+
+        trie.retain(|_, metadata| !metadata.obscure);
+        assert_eq!(trie.words_with_prefix(""), vec!["the", "thee", "then"]);
+        assert!(!trie.find(b"thec").has_longer_words && !trie.find(b"thec").is_word);
+
+        trie.retain(|word, metadata| metadata.is_common(50) || word.len() > 3);
+        assert_eq!(trie.words_with_prefix(""), vec!["the", "thee", "then"]);
+
+        trie.retain(|_, metadata| metadata.is_common(50));
+        assert_eq!(trie.words_with_prefix(""), vec!["the"]);
+        assert!(!trie.find(b"the").has_longer_words);
+    }
+}
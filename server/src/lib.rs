@@ -1,8 +1,8 @@
-mod word;
-mod node_value_cache;
+pub mod word;
+pub mod value_cache;
 mod outcomes;
 mod tree;
-mod peg_solitaire;
+pub mod peg_solitaire;
 mod min_max;
 mod dyn_prog;
 pub mod games;
@@ -1,18 +1,62 @@
 use actix_web::{web, App, HttpServer};
 
+use std::env;
+use std::path::Path;
+
 use rust_game_ai::analysis_server::{
+    evaluate_peg_position,
     find_best_action,
+    json_config,
+    query_words,
+    solve_boggle,
+    wordle_candidates,
+    wordle_suggest,
 };
+use rust_game_ai::games::boggle_like::analyst::{BoggleAnalyst};
 use rust_game_ai::games::shut_the_box::{ShutTheBoxAnalyst};
+use rust_game_ai::peg_solitaire::analyst::{PegSolitaireAnalyst};
+use rust_game_ai::word::analyst::{WordAnalyst, WordleAnalyst};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     let port = 8383;
     let address = format!("127.0.0.1:{:?}", port);
 
-    HttpServer::new(|| {
+    // dictionaries are large, so load them once and share them between workers
+    let dictionary_dir = env::var("BOGGLE_DICTIONARY_DIR")
+        .unwrap_or_else(|_| "dictionaries".to_owned());
+    println!("loading dictionaries from {}...", dictionary_dir);
+    let boggle_analyst = web::Data::new(BoggleAnalyst::load(&dictionary_dir).unwrap_or_else(|e| {
+        println!("couldn't load dictionaries: {}", e);
+        BoggleAnalyst::new(vec![])
+    }));
+    println!("dictionaries ready: {:?}", boggle_analyst.dictionary_names());
+
+    // the same word lists, indexed by the letter in each slot
+    let word_analyst = web::Data::new(WordAnalyst::load(&dictionary_dir).unwrap_or_else(|e| {
+        println!("couldn't index dictionaries: {}", e);
+        WordAnalyst::new(vec![])
+    }));
+    println!("word indexes ready: {:?}", word_analyst.dictionary_names());
+
+    // Wordle has its own answer list, and optionally a list of the other
+    // words it accepts as guesses
+    let wordle_answers = env::var("WORDLE_ANSWERS")
+        .unwrap_or_else(|_| format!("{}/wordle/answers.txt", dictionary_dir));
+    let wordle_guesses = env::var("WORDLE_GUESSES")
+        .unwrap_or_else(|_| format!("{}/wordle/guesses.txt", dictionary_dir));
+    let wordle_guesses = Some(wordle_guesses).filter(|path| Path::new(path).exists());
+    println!("loading Wordle answers from {}...", wordle_answers);
+    let wordle_analyst = web::Data::new(WordleAnalyst::load(wordle_answers, wordle_guesses).unwrap_or_else(|e| {
+        println!("couldn't load Wordle answers: {}", e);
+        WordleAnalyst::new(vec![], &[]).unwrap()
+    }));
+    println!("Wordle ready with {} answers", wordle_analyst.answer_count());
+
+    HttpServer::new(move || {
         println!("creating analyst...");
         let analyst = ShutTheBoxAnalyst::new();
+        let peg_analyst = PegSolitaireAnalyst::new();
         println!("analyst ready!");
 
         println!("starting worker...");
@@ -22,6 +66,29 @@ async fn main() -> std::io::Result<()> {
                     .data(analyst)
                     .route("/find-best-action", web::post().to(find_best_action))
             )
+            .service(
+                web::scope("/peg-solitaire")
+                    .data(peg_analyst)
+                    .route("/evaluate-position", web::post().to(evaluate_peg_position))
+            )
+            .service(
+                web::scope("/boggle")
+                    .app_data(boggle_analyst.clone())
+                    .route("/solve", web::post().to(solve_boggle))
+            )
+            .service(
+                web::scope("/words")
+                    .app_data(word_analyst.clone())
+                    .app_data(json_config())
+                    .route("/query", web::post().to(query_words))
+            )
+            .service(
+                web::scope("/wordle")
+                    .app_data(wordle_analyst.clone())
+                    .app_data(json_config())
+                    .route("/candidates", web::post().to(wordle_candidates))
+                    .route("/suggest", web::post().to(wordle_suggest))
+            )
     })
     .workers(2)
     .bind(address.to_owned())?
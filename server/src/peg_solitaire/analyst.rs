@@ -0,0 +1,156 @@
+use std::fmt;
+use std::time::Duration;
+
+use super::goal::PegGoal;
+use super::peg_board::PegBoard;
+use super::solver::{evaluate_position, PositionEvaluation};
+
+#[derive(Debug, PartialEq)]
+pub enum PegAnalysisError {
+    UnknownBoard(String),
+    WrongHoleCount { expected: usize, actual: usize },
+    InvalidTargetHole(usize),
+}
+
+impl fmt::Display for PegAnalysisError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PegAnalysisError::UnknownBoard(name) => {
+                write!(formatter, "unknown board '{}'", name)
+            },
+            PegAnalysisError::WrongHoleCount { expected, actual } => {
+                write!(formatter, "expected {} holes but got {}", expected, actual)
+            },
+            PegAnalysisError::InvalidTargetHole(hole) => {
+                write!(formatter, "target hole {} is not on the board", hole)
+            },
+        }
+    }
+}
+
+/// How long an evaluation may search before it answers with what it has.
+pub const EVALUATION_TIME_LIMIT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone)]
+pub struct PegSolitaireAnalyst {
+    boards: Vec<(String, PegBoard)>,
+    time_limit: Duration,
+}
+
+impl PegSolitaireAnalyst {
+    pub fn new() -> PegSolitaireAnalyst {
+        PegSolitaireAnalyst {
+            boards: vec![
+                ("triangle".to_owned(), PegBoard::triangle()),
+                ("english".to_owned(), PegBoard::english()),
+                ("european".to_owned(), PegBoard::european()),
+                ("wiegleb".to_owned(), PegBoard::wiegleb()),
+                ("diamond".to_owned(), PegBoard::diamond()),
+            ],
+            time_limit: EVALUATION_TIME_LIMIT,
+        }
+    }
+
+    pub fn with_time_limit(self, time_limit: Duration) -> PegSolitaireAnalyst {
+        PegSolitaireAnalyst {
+            time_limit,
+            ..self
+        }
+    }
+
+    pub fn board(&self, name: &str) -> Option<&PegBoard> {
+        self.boards.iter()
+            .find(|(board_name, _)| board_name == name)
+            .map(|(_, board)| board)
+    }
+
+    /// Evaluates the position given as one flag per hole, `true` where
+    /// there is a peg, searching for at most the analyst's time limit.
+    pub fn evaluate(
+        &self,
+        board_name: &str,
+        holes: &[bool],
+        target_hole: Option<usize>,
+    ) -> Result<PositionEvaluation, PegAnalysisError> {
+        let board = self.board(board_name)
+            .ok_or_else(|| PegAnalysisError::UnknownBoard(board_name.to_owned()))?;
+
+        if holes.len() != board.hole_count() {
+            return Err(PegAnalysisError::WrongHoleCount {
+                expected: board.hole_count(),
+                actual: holes.len(),
+            });
+        }
+
+        if let Some(hole) = target_hole.filter(|h| *h >= board.hole_count()) {
+            return Err(PegAnalysisError::InvalidTargetHole(hole));
+        }
+
+        let goal = match target_hole {
+            Some(hole) => PegGoal::FinishInHole(hole),
+            None => PegGoal::SinglePeg,
+        };
+
+        Ok(evaluate_position(board, board.from_holes(holes), goal, Some(self.time_limit)))
+    }
+}
+
+impl Default for PegSolitaireAnalyst {
+    fn default() -> Self {
+        PegSolitaireAnalyst::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peg_solitaire::peg_board::PegMove;
+
+    #[test]
+    fn test_evaluate() {
+        let analyst = PegSolitaireAnalyst::new();
+        let mut holes = [true; 15];
+        holes[0] = false;
+
+        let evaluation = analyst.evaluate("triangle", &holes, Some(0)).unwrap();
+
+        assert_eq!(evaluation.is_solvable, Some(true));
+        assert_eq!(evaluation.winning_moves, vec![
+            PegMove { from: 3, over: 1, to: 0 },
+            PegMove { from: 5, over: 2, to: 0 },
+        ]);
+    }
+
+    #[test]
+    fn test_evaluate_gives_up_after_time_limit() {
+        // the European board can't be solved from its center, and proving
+        // that takes far longer than the limit
+        let analyst = PegSolitaireAnalyst::new().with_time_limit(Duration::from_millis(100));
+        let mut holes = [true; 37];
+        holes[18] = false;
+
+        let evaluation = analyst.evaluate("european", &holes, None).unwrap();
+
+        assert_eq!(evaluation.is_solvable, None);
+        assert_eq!(evaluation.min_reachable_pegs, None);
+        assert_eq!(evaluation.winning_moves, vec![]);
+    }
+
+    #[test]
+    fn test_evaluate_rejects_bad_input() {
+        let analyst = PegSolitaireAnalyst::new();
+
+        assert_eq!(
+            analyst.evaluate("hexagon", &[true; 15], None),
+            Err(PegAnalysisError::UnknownBoard("hexagon".to_owned())),
+        );
+        assert_eq!(
+            analyst.evaluate("english", &[true; 15], None),
+            Err(PegAnalysisError::WrongHoleCount { expected: 33, actual: 15 }),
+        );
+        assert_eq!(
+            analyst.evaluate("triangle", &[true; 15], Some(15)),
+            Err(PegAnalysisError::InvalidTargetHole(15)),
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use super::peg_board::{Lattice, PegBoard};
+
+pub const ENGLISH_CENTER: usize = 16;
+pub const EUROPEAN_CENTER: usize = 18;
+pub const WIEGLEB_CENTER: usize = 22;
+pub const DIAMOND_CENTER: usize = 20;
+
+impl PegBoard {
+    /// The 15-hole triangle ("cracker barrel") board.
+    ///
+    /// ```text
+    ///         0
+    ///       1   2
+    ///     3   4   5
+    ///   6   7   8   9
+    /// 10  11  12  13  14
+    /// ```
+    pub fn triangle() -> PegBoard {
+        PegBoard::from_layout(&[
+            "o",
+            "oo",
+            "ooo",
+            "oooo",
+            "ooooo",
+        ], Lattice::Triangular)
+    }
+
+    /// The 33-hole English cross.
+    pub fn english() -> PegBoard {
+        PegBoard::from_layout(&[
+            "  ooo  ",
+            "  ooo  ",
+            "ooooooo",
+            "ooooooo",
+            "ooooooo",
+            "  ooo  ",
+            "  ooo  ",
+        ], Lattice::Square)
+    }
+
+    /// The 37-hole European (French) board.
+    pub fn european() -> PegBoard {
+        PegBoard::from_layout(&[
+            "  ooo  ",
+            " ooooo ",
+            "ooooooo",
+            "ooooooo",
+            "ooooooo",
+            " ooooo ",
+            "  ooo  ",
+        ], Lattice::Square)
+    }
+
+    /// Wiegleb's 45-hole cross, the English board with longer arms.
+    pub fn wiegleb() -> PegBoard {
+        PegBoard::from_layout(&[
+            "   ooo   ",
+            "   ooo   ",
+            "   ooo   ",
+            "ooooooooo",
+            "ooooooooo",
+            "ooooooooo",
+            "   ooo   ",
+            "   ooo   ",
+            "   ooo   ",
+        ], Lattice::Square)
+    }
+
+    /// The 41-hole diamond.
+    pub fn diamond() -> PegBoard {
+        PegBoard::from_layout(&[
+            "    o    ",
+            "   ooo   ",
+            "  ooooo  ",
+            " ooooooo ",
+            "ooooooooo",
+            " ooooooo ",
+            "  ooooo  ",
+            "   ooo   ",
+            "    o    ",
+        ], Lattice::Square)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peg_solitaire::peg_board::PegMove;
+
+    #[test]
+    fn test_board_sizes() {
+        let sut: Vec<(PegBoard, usize, usize, usize)> = vec![
+            // (board, holes, jumps, symmetries)
+            (PegBoard::triangle(), 15, 36, 6),
+            (PegBoard::english(), 33, 76, 8),
+            (PegBoard::european(), 37, 92, 8),
+            (PegBoard::wiegleb(), 45, 108, 8),
+            (PegBoard::diamond(), 41, 100, 8),
+        ];
+
+        for (board, holes, jumps, symmetries) in sut {
+            assert_eq!(board.hole_count(), holes);
+            assert_eq!(board.jumps().len(), jumps);
+            assert_eq!(board.symmetry_count(), symmetries);
+        }
+    }
+
+    #[test]
+    fn test_centers() {
+        let sut: Vec<(PegBoard, usize, (i32, i32))> = vec![
+            (PegBoard::english(), ENGLISH_CENTER, (3, 3)),
+            (PegBoard::european(), EUROPEAN_CENTER, (3, 3)),
+            (PegBoard::wiegleb(), WIEGLEB_CENTER, (4, 4)),
+            (PegBoard::diamond(), DIAMOND_CENTER, (4, 4)),
+        ];
+
+        for (board, center, coordinates) in sut {
+            assert_eq!(board.coordinates(center), coordinates);
+            assert_eq!(board.symmetries_fixing(&[center]).len(), 8);
+        }
+    }
+
+    #[test]
+    fn test_triangle_jumps_match_hand_coded_rules() {
+        let board = PegBoard::triangle();
+        let pegs = board.with_empty_hole(12);
+
+        let expected_moves = vec![
+            PegMove { from: 3, over: 7, to: 12 },
+            PegMove { from: 5, over: 8, to: 12 },
+            PegMove { from: 10, over: 11, to: 12 },
+            PegMove { from: 14, over: 13, to: 12 },
+        ];
+
+        assert_eq!(board.legal_moves(pegs), expected_moves);
+    }
+
+    #[test]
+    fn test_english_opening_moves() {
+        let board = PegBoard::english();
+        let pegs = board.with_empty_hole(ENGLISH_CENTER);
+
+        let expected_moves = vec![
+            PegMove { from: 4, over: 9, to: 16 },
+            PegMove { from: 14, over: 15, to: 16 },
+            PegMove { from: 18, over: 17, to: 16 },
+            PegMove { from: 28, over: 23, to: 16 },
+        ];
+
+        assert_eq!(board.legal_moves(pegs), expected_moves);
+    }
+
+    #[test]
+    fn test_jumps_follow_board_lines() {
+        for board in [PegBoard::english(), PegBoard::european(), PegBoard::wiegleb(), PegBoard::diamond()] {
+            for jump in board.jumps() {
+                let (from_row, from_col) = board.coordinates(jump.from);
+                let (over_row, over_col) = board.coordinates(jump.over);
+                let (to_row, to_col) = board.coordinates(jump.to);
+
+                assert_eq!((from_row + to_row, from_col + to_col), (2 * over_row, 2 * over_col));
+                assert_eq!((from_row - over_row).abs() + (from_col - over_col).abs(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_canonical_is_shared_by_symmetric_positions() {
+        let board = PegBoard::english();
+        let symmetries = board.symmetries_fixing(&[ENGLISH_CENTER]);
+        let pegs = board.with_empty_hole(ENGLISH_CENTER);
+
+        let after_up = board.after_move(pegs, &PegMove { from: 4, over: 9, to: 16 });
+        let after_left = board.after_move(pegs, &PegMove { from: 14, over: 15, to: 16 });
+        let after_down = board.after_move(pegs, &PegMove { from: 28, over: 23, to: 16 });
+
+        assert_ne!(after_up, after_left);
+        assert_eq!(board.canonical(after_up, &symmetries), board.canonical(after_left, &symmetries));
+        assert_eq!(board.canonical(after_up, &symmetries), board.canonical(after_down, &symmetries));
+        assert_eq!(board.canonical(pegs, &symmetries), pegs);
+    }
+}
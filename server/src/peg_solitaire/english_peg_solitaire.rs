@@ -1,290 +1,160 @@
-use crate::tree::TreeEvaluator;
-use crate::tree::TreeNode;
-use crate::outcomes::BinaryOutcome;
-use crate::node_value_cache::InMemoryNodeValueCache;
+use std::sync::OnceLock;
+
+use super::boards::ENGLISH_CENTER;
+use super::goal::PegGoal;
+use super::peg_board::{PegBoard, PegMove, count_pegs, has_peg, hole_bit};
+use super::solver::solve;
+
+/// One bit per hole, numbered row by row from the top left:
+///
+/// ```text
+///         0  1  2
+///         3  4  5
+///   6  7  8  9 10 11 12
+///  13 14 15 16 17 18 19
+///  20 21 22 23 24 25 26
+///        27 28 29
+///        30 31 32
+/// ```
+pub use super::peg_board::BitBoard;
+
+pub const HOLE_COUNT: usize = 33;
+pub const CENTER_HOLE: usize = ENGLISH_CENTER;
+
+pub type EnglishPegMove = PegMove;
+
+/// The English cross, built once and shared by every `EnglishPegState`.
+pub fn english() -> &'static PegBoard {
+    static BOARD: OnceLock<PegBoard> = OnceLock::new();
+    BOARD.get_or_init(PegBoard::english)
+}
 
-type Board = [bool; 33];
-#[derive(Clone)]
-struct EnglishPegState {
-    board: Board
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnglishPegState {
+    board: BitBoard
 }
 
 impl EnglishPegState {
-    fn new() -> EnglishPegState {
+    /// The standard opening position: every hole filled except the center.
+    pub fn new() -> EnglishPegState {
+        EnglishPegState::with_empty_hole(CENTER_HOLE)
+    }
+
+    pub fn with_empty_hole(empty_hole: usize) -> EnglishPegState {
         EnglishPegState {
-            board: make_starting_board()
+            board: english().with_empty_hole(empty_hole)
         }
     }
-}
 
-impl PartialEq for EnglishPegState {
-    fn eq(&self, other: &Self) -> bool {
-        self.board[..] == other.board[..]
+    pub fn from_holes(holes: &[bool; HOLE_COUNT]) -> EnglishPegState {
+        EnglishPegState {
+            board: english().from_holes(holes)
+        }
     }
-}
 
-impl std::fmt::Debug for EnglishPegState {
-    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        self.board[..].fmt(formatter)
+    pub fn has_peg(&self, hole: usize) -> bool {
+        has_peg(self.board, hole)
     }
-}
 
-fn make_starting_board() -> Board {
-    let mut board: Board = [true; 33];
-    board[16] = false;
-    board
-}
-
-fn count_pegs(board: &Board) -> u32 {
-    let mut count = 0;
-    for p in board.iter() {
-        if *p { count += 1; }
+    pub fn count_pegs(&self) -> u32 {
+        count_pegs(self.board)
     }
-    count
-}
-
-fn get_legal_moves(board: &Board) -> Vec<EnglishPegMove> {
-    let mut legal_moves = Vec::new();
 
-    for (i,p) in board.iter().enumerate() {
-        if *p {
-            if let Some(left) = get_left_move(board, i) {
-                legal_moves.push(left);
-            }
-            if let Some(right) = get_right_move(board, i) {
-                legal_moves.push(right);
-            }
-            if let Some(up) = get_up_move(board, i) {
-                legal_moves.push(up);
-            }
-            if let Some(down) = get_down_move(board, i) {
-                legal_moves.push(down);
-            }
-        }
+    pub fn is_center_finish(&self) -> bool {
+        self.board == hole_bit(CENTER_HOLE)
     }
 
-    legal_moves
-}
-
-fn get_left_move(board: &Board, index: usize) -> Option<EnglishPegMove> {
-    
-    let has_two_on_left = match index {
-        2|5|29|32 => true,
-        x if x >= 8 && x <= 12 => true,
-        x if x >= 15 && x <= 19 => true,
-        x if x >= 22 && x <= 26 => true,
-        _ => false
-    };
-
-    if has_two_on_left && board[index - 1] && !board[index - 2] {
-        Some(EnglishPegMove {from: index, over: index - 1, to: index - 2})
-    } else {
-        None
+    pub fn legal_moves(&self) -> Vec<EnglishPegMove> {
+        english().legal_moves(self.board)
     }
-}
-
-fn get_right_move(board: &Board, index: usize) -> Option<EnglishPegMove> {
-    let has_two_on_right = match index {
-        0|3|27|30 => true,
-        x if x >= 6 && x <= 10 => true,
-        x if x >= 13 && x <= 17 => true,
-        x if x >= 20 && x <= 24 => true,
-        _ => false
-    };
 
-    if has_two_on_right && board[index + 1] && !board[index + 2] {
-        Some(EnglishPegMove {from: index, over: index + 1, to: index + 2})
-    } else {
-        None
-    }
-}
-
-fn get_up_move(board: &Board, index: usize) -> Option<EnglishPegMove> {
-    match index {
-        x if x >= 8 && x <= 10 => {
-            if board[index - 5] && !board[index - 8] {
-                Some(EnglishPegMove {from: index, over: index - 5, to: index - 8})
-            } else {
-                None
-            }
-        },
-        x if x >= 15 && x <= 17 => {
-            if board[index - 7] && !board[index - 12] {
-                Some(EnglishPegMove {from: index, over: index - 7, to: index - 12})
-            } else {
-                None
-            }
-        },
-        x if x >= 20 && x <= 26 => {
-            if board[index - 7] && !board[index - 14] {
-                Some(EnglishPegMove {from: index, over: index - 7, to: index - 14})
-            } else {
-                None
-            }
-        },
-        x if x >= 27 && x <= 29 => {
-            if board[index - 5] && !board[index - 12] {
-                Some(EnglishPegMove {from: index, over: index - 5, to: index - 12})
-            } else {
-                None
-            }
-        },
-        x if x >= 30 && x <= 32 => {
-            if board[index - 3] && !board[index - 8] {
-                Some(EnglishPegMove {from: index, over: index - 3, to: index - 8})
-            } else {
-                None
-            }
+    pub fn after_move(&self, action: &EnglishPegMove) -> EnglishPegState {
+        EnglishPegState {
+            board: english().after_move(self.board, action)
         }
-        _ => None
     }
-}
-
 
-fn get_down_move(board: &Board, index: usize) -> Option<EnglishPegMove> {
-    if index >= 0 && index <= 2 {
-        if board[index + 3] && !board[index + 5] {
-            Some(EnglishPegMove {from: index, over: index + 3, to: index + 5})
-        } else {
-            None
-        }
-    } else if index >= 3 && index <= 5 {
-        if board[index + 5] && !board[index + 12] {
-            Some(EnglishPegMove {from: index, over: index + 5, to: index + 12})
-        } else {
-            None
-        }
-    } else if index >= 6 && index <= 12 {
-        if board[index + 7] && !board[index + 14] {
-            Some(EnglishPegMove {from: index, over: index + 7, to: index + 14})
-        } else {
-            None
-        }
-    } else if index >= 15 && index <= 17 {
-        if board[index + 7] && !board[index + 12] {
-            Some(EnglishPegMove {from: index, over: index + 7, to: index + 12})
-        } else {
-            None
-        }
-    } else if index >= 22 && index <= 24 {
-        if board[index + 5] && !board[index + 8] {
-            Some(EnglishPegMove {from: index, over: index + 5, to: index + 8})
-        } else {
-            None
-        }
-    } else {
-        None
+    /// The smallest board among the 8 rotations and reflections of this one.
+    ///
+    /// Every symmetry fixes the center hole, so positions with the same
+    /// canonical board are either all center-solvable or none are.
+    pub fn canonical(&self) -> BitBoard {
+        let board = english();
+        board.canonical(self.board, &board.symmetries_fixing(&[CENTER_HOLE]))
     }
 }
 
-fn board_after_move(board: &Board, action: EnglishPegMove) -> Board {
-    let mut board_copy = [false; 33];
-    board_copy.copy_from_slice(board);
-
-    if !board_copy[action.from] || !board_copy[action.over] || board_copy[action.to] {
-        panic!("oops - this move isn't valid");
+impl Default for EnglishPegState {
+    fn default() -> Self {
+        EnglishPegState::new()
     }
-
-    board_copy[action.from] = false;
-    board_copy[action.over] = false;
-    board_copy[action.to] = true;
-
-    board_copy
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct EnglishPegMove {
-    from: usize,
-    over: usize,
-    to: usize
+impl std::fmt::Debug for EnglishPegState {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "{:033b}", self.board)
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-struct EnglishPegNode {
-    state: EnglishPegState,
-    legal_moves: Vec<EnglishPegMove>,
-    move_index: usize,
-    all_children_lose: bool
+/// Finds a sequence of jumps from `start` that leaves a single peg in the
+/// center hole, or `None` if there isn't one.
+pub fn solve_center_finish(start: &EnglishPegState) -> Option<Vec<EnglishPegMove>> {
+    solve(english(), start.board, PegGoal::FinishInHole(CENTER_HOLE))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl TreeNode<EnglishPegNode, BinaryOutcome> for EnglishPegNode {
-    fn on_child_pruned(&mut self, child: EnglishPegNode, child_value: BinaryOutcome) {
-        self.all_children_lose = self.all_children_lose && child_value == BinaryOutcome::Lose;
-        self.move_index += 1;
+    fn replay(start: &EnglishPegState, moves: &[EnglishPegMove]) -> EnglishPegState {
+        moves.iter().fold(*start, |state, action| {
+            assert!(state.legal_moves().contains(action));
+            state.after_move(action)
+        })
     }
 
-    fn request_next_child(&mut self) -> Option<EnglishPegNode> {
-        if self.move_index < self.legal_moves.len() {
-            let current_move = self.legal_moves[self.move_index];
-            let next_board = board_after_move(&self.state.board, current_move);
-            let next_legal_moves = get_legal_moves(&next_board);
-            let next_state = EnglishPegState {
-                board: next_board
-            };
-
-            Some(EnglishPegNode {
-                state: next_state,
-                legal_moves: next_legal_moves,
-                move_index: 0,
-                all_children_lose: true
-            })
-        } else {
-            None
-        }
+    #[test]
+    fn test_opening_moves() {
+        let mut opening_moves = EnglishPegState::new().legal_moves();
+        opening_moves.sort_by_key(|action| action.from);
+
+        let expected_opening_moves = vec![
+            EnglishPegMove { from: 4, over: 9, to: 16 },
+            EnglishPegMove { from: 14, over: 15, to: 16 },
+            EnglishPegMove { from: 18, over: 17, to: 16 },
+            EnglishPegMove { from: 28, over: 23, to: 16 },
+        ];
+        assert_eq!(opening_moves, expected_opening_moves);
     }
 
-    fn on_all_children_pruned(&mut self) -> BinaryOutcome {
-        if count_pegs(&self.state.board) == 1 || !self.all_children_lose {
-            BinaryOutcome::Win
-        } else {
-            BinaryOutcome::Lose
-        }
+    #[test]
+    fn test_canonical_is_shared_by_symmetric_positions() {
+        let state = EnglishPegState::new();
+        let after_up = state.after_move(&EnglishPegMove { from: 4, over: 9, to: 16 });
+        let after_left = state.after_move(&EnglishPegMove { from: 14, over: 15, to: 16 });
+        let after_down = state.after_move(&EnglishPegMove { from: 28, over: 23, to: 16 });
+
+        assert_ne!(after_up, after_left);
+        assert_eq!(after_up.canonical(), after_left.canonical());
+        assert_eq!(after_up.canonical(), after_down.canonical());
+        assert_eq!(state.canonical(), state.board);
     }
-}
 
-mod tests {
-    use super::*;
+    #[test]
+    fn test_english_peg_search() {
+        let start = EnglishPegState::new();
+        let solution = solve_center_finish(&start).unwrap();
 
-    fn get_key_for_node(node: &EnglishPegNode) -> String {
-        node.state.board.iter().map(|x| {
-            if *x {
-                '1'
-            } else {
-                '0'
-            }
-        }).collect()
+        assert_eq!(solution.len(), 31);
+        assert!(replay(&start, &solution).is_center_finish());
     }
 
-    // #[test]
-    // fn test_english_peg_search() {
-    //     let state = EnglishPegState::new();
-    //     let legal_moves = get_legal_moves(&state.board);
-
-    //     let root_node = EnglishPegNode {
-    //         state,
-    //         legal_moves,
-    //         move_index: 0,
-    //         all_children_lose: true
-    //     };
-
-    //     let node_value_cache = InMemoryNodeValueCache::new(get_key_for_node);
+    #[test]
+    fn test_unsolvable_position() {
+        let mut holes = [false; HOLE_COUNT];
+        holes[0] = true;
+        holes[32] = true;
 
-    //     let mut tree = TreeEvaluator::<EnglishPegNode, BinaryOutcome, InMemoryNodeValueCache<EnglishPegNode, String, BinaryOutcome>>::new(
-    //         root_node,
-    //         node_value_cache,
-    //         100,
-    //         Some(BinaryOutcome::Win)
-    //     );
-
-    //     tree.search_with_max_visits(1000000000, get_key_for_node);
-    //     let action_list: Vec<&EnglishPegMove> = tree.get_tree_path().iter().map(|x| {
-    //         &x.legal_moves[x.move_index]
-    //     }).collect();
-
-    //     //println!("action list {:?}", action_list);
-
-    //     tree.print_cache();
-    // }
-
-}
\ No newline at end of file
+        assert_eq!(solve_center_finish(&EnglishPegState::from_holes(&holes)), None);
+    }
+}
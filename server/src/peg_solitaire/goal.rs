@@ -0,0 +1,148 @@
+use super::peg_board::{BitBoard, PegBoard, count_pegs, hole_bit};
+
+/// What a peg solitaire puzzle asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PegGoal {
+    /// A single peg left anywhere on the board.
+    SinglePeg,
+    /// A single peg left in the given hole.
+    FinishInHole(usize),
+    /// The game ends - no legal moves remain - with exactly this many pegs.
+    LeaveExactly(u32),
+    /// Reach exactly this arrangement of pegs.
+    Pattern(BitBoard),
+}
+
+impl PegGoal {
+    pub fn is_reached(&self, board: &PegBoard, pegs: BitBoard) -> bool {
+        match self {
+            PegGoal::SinglePeg => count_pegs(pegs) == 1,
+            PegGoal::FinishInHole(hole) => pegs == hole_bit(*hole),
+            PegGoal::LeaveExactly(n) => {
+                count_pegs(pegs) == *n && board.legal_moves(pegs).is_empty()
+            },
+            PegGoal::Pattern(pattern) => pegs == *pattern,
+        }
+    }
+
+    /// Every move removes a peg, so once a position has no more pegs than
+    /// the goal and isn't the goal, it never will be.
+    pub fn is_out_of_reach(&self, board: &PegBoard, pegs: BitBoard) -> bool {
+        count_pegs(pegs) <= self.peg_count() && !self.is_reached(board, pegs)
+    }
+
+    pub fn peg_count(&self) -> u32 {
+        match self {
+            PegGoal::SinglePeg => 1,
+            PegGoal::FinishInHole(_) => 1,
+            PegGoal::LeaveExactly(n) => *n,
+            PegGoal::Pattern(pattern) => count_pegs(*pattern),
+        }
+    }
+
+    /// The symmetries of the board that map goal positions to goal
+    /// positions, so positions related by them share a value.
+    pub fn symmetries(&self, board: &PegBoard) -> Vec<usize> {
+        match self {
+            PegGoal::SinglePeg => board.symmetries_fixing(&[]),
+            PegGoal::FinishInHole(hole) => board.symmetries_fixing(&[*hole]),
+            PegGoal::LeaveExactly(_) => board.symmetries_fixing(&[]),
+            PegGoal::Pattern(pattern) => {
+                board.symmetries_fixing(&[])
+                    .into_iter()
+                    .filter(|s| board.apply_symmetry(*s, *pattern) == *pattern)
+                    .collect()
+            },
+        }
+    }
+
+    /// Every position that satisfies the goal.
+    ///
+    /// For `LeaveExactly(n)` this walks all n-peg positions, so it is only
+    /// practical for small n.
+    pub fn goal_states(&self, board: &PegBoard) -> Vec<BitBoard> {
+        match self {
+            PegGoal::SinglePeg => {
+                (0..board.hole_count()).map(hole_bit).collect()
+            },
+            PegGoal::FinishInHole(hole) => vec![hole_bit(*hole)],
+            PegGoal::LeaveExactly(n) => {
+                let mut states = vec![];
+                collect_positions(board, *n, 0, 0, &mut states);
+                states.retain(|pegs| board.legal_moves(*pegs).is_empty());
+                states
+            },
+            PegGoal::Pattern(pattern) => vec![*pattern],
+        }
+    }
+}
+
+fn collect_positions(
+    board: &PegBoard,
+    pegs_left: u32,
+    next_hole: usize,
+    pegs: BitBoard,
+    states: &mut Vec<BitBoard>,
+) {
+    if pegs_left == 0 {
+        states.push(pegs);
+        return;
+    }
+
+    let last_start = board.hole_count().saturating_sub(pegs_left as usize);
+    for hole in next_hole..=last_start {
+        collect_positions(board, pegs_left - 1, hole + 1, pegs | hole_bit(hole), states);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peg_solitaire::boards::ENGLISH_CENTER;
+
+    #[test]
+    fn test_is_reached() {
+        let board = PegBoard::english();
+
+        let center = hole_bit(ENGLISH_CENTER);
+        assert!(PegGoal::SinglePeg.is_reached(&board, center));
+        assert!(PegGoal::FinishInHole(ENGLISH_CENTER).is_reached(&board, center));
+        assert!(!PegGoal::FinishInHole(0).is_reached(&board, center));
+
+        // 0 and 1 can still jump, 0 and 32 can't
+        let adjacent = hole_bit(0) | hole_bit(1);
+        let apart = hole_bit(0) | hole_bit(32);
+        assert!(!PegGoal::LeaveExactly(2).is_reached(&board, adjacent));
+        assert!(PegGoal::LeaveExactly(2).is_reached(&board, apart));
+
+        assert!(PegGoal::Pattern(apart).is_reached(&board, apart));
+        assert!(!PegGoal::Pattern(apart).is_reached(&board, adjacent));
+    }
+
+    #[test]
+    fn test_symmetries() {
+        let board = PegBoard::english();
+
+        assert_eq!(PegGoal::SinglePeg.symmetries(&board).len(), 8);
+        assert_eq!(PegGoal::FinishInHole(ENGLISH_CENTER).symmetries(&board).len(), 8);
+        // the middle of the top edge is only fixed by the identity and the
+        // left-right reflection
+        assert_eq!(PegGoal::FinishInHole(1).symmetries(&board).len(), 2);
+        assert_eq!(PegGoal::Pattern(hole_bit(0) | hole_bit(2)).symmetries(&board).len(), 2);
+    }
+
+    #[test]
+    fn test_goal_states() {
+        let board = PegBoard::triangle();
+
+        assert_eq!(PegGoal::SinglePeg.goal_states(&board).len(), 15);
+        assert_eq!(PegGoal::FinishInHole(4).goal_states(&board), vec![hole_bit(4)]);
+
+        let two_peg_endings = PegGoal::LeaveExactly(2).goal_states(&board);
+        assert!(two_peg_endings.iter().all(|pegs| count_pegs(*pegs) == 2));
+        assert!(two_peg_endings.iter().all(|pegs| board.legal_moves(*pegs).is_empty()));
+        // 105 pairs of holes, 27 of them next to each other with room for
+        // one to jump the other
+        assert_eq!(two_peg_endings.len(), 105 - 27);
+    }
+}
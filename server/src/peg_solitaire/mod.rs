@@ -1,2 +1,7 @@
-pub mod triangle_peg_solitaire;
-pub mod english_peg_solitaire;
\ No newline at end of file
+pub mod peg_board;
+pub mod boards;
+pub mod english_peg_solitaire;
+pub mod goal;
+pub mod solver;
+pub mod reverse_search;
+pub mod analyst;
\ No newline at end of file
@@ -0,0 +1,325 @@
+/// One bit per hole, in the order the holes appear on the board layout.
+pub type BitBoard = u64;
+
+pub const MAX_HOLES: usize = 64;
+
+/// How holes are connected to their neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lattice {
+    /// Jumps run along rows and columns.
+    Square,
+    /// Holes in row `r` sit in columns `0..=r` of the layout, and jumps run
+    /// along rows and both diagonals of the triangle.
+    Triangular,
+}
+
+impl Lattice {
+    fn directions(&self) -> Vec<(i32, i32)> {
+        match self {
+            Lattice::Square => vec![
+                (0, -1),
+                (0, 1),
+                (-1, 0),
+                (1, 0),
+            ],
+            Lattice::Triangular => vec![
+                (0, -1),
+                (0, 1),
+                (-1, 0),
+                (1, 0),
+                (-1, -1),
+                (1, 1),
+            ],
+        }
+    }
+
+    // Candidate rotations and reflections of a layout whose bounding box
+    // spans 0..=extent in each axis.
+    fn transforms(&self, extent: i32) -> Vec<Transform> {
+        let n = extent;
+        match self {
+            Lattice::Square => vec![
+                Box::new(move |r, c| (r, c)),
+                Box::new(move |r, c| (c, n - r)),
+                Box::new(move |r, c| (n - r, n - c)),
+                Box::new(move |r, c| (n - c, r)),
+                Box::new(move |r, c| (r, n - c)),
+                Box::new(move |r, c| (n - r, c)),
+                Box::new(move |r, c| (c, r)),
+                Box::new(move |r, c| (n - c, n - r)),
+            ],
+            Lattice::Triangular => {
+                // Permute the three distances to the sides of the triangle.
+                let to_sides = move |r: i32, c: i32| [c, r - c, n - r];
+                let from_sides = |x: i32, y: i32| (x + y, x);
+                vec![
+                    Box::new(move |r, c| { let [x, y, _] = to_sides(r, c); from_sides(x, y) }),
+                    Box::new(move |r, c| { let [x, _, z] = to_sides(r, c); from_sides(x, z) }),
+                    Box::new(move |r, c| { let [x, y, _] = to_sides(r, c); from_sides(y, x) }),
+                    Box::new(move |r, c| { let [_, y, z] = to_sides(r, c); from_sides(y, z) }),
+                    Box::new(move |r, c| { let [x, _, z] = to_sides(r, c); from_sides(z, x) }),
+                    Box::new(move |r, c| { let [_, y, z] = to_sides(r, c); from_sides(z, y) }),
+                ]
+            },
+        }
+    }
+}
+
+type Transform = Box<dyn Fn(i32, i32) -> (i32, i32)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PegMove {
+    pub from: usize,
+    pub over: usize,
+    pub to: usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct JumpMask {
+    action: PegMove,
+    from_over: BitBoard,
+    to: BitBoard,
+}
+
+impl JumpMask {
+    fn new(action: PegMove) -> JumpMask {
+        JumpMask {
+            action,
+            from_over: hole_bit(action.from) | hole_bit(action.over),
+            to: hole_bit(action.to),
+        }
+    }
+
+    fn is_legal(&self, pegs: BitBoard) -> bool {
+        pegs & self.from_over == self.from_over && pegs & self.to == 0
+    }
+}
+
+pub fn hole_bit(hole: usize) -> BitBoard {
+    1 << hole
+}
+
+/// A peg solitaire board: where the holes are and which jumps connect them.
+///
+/// Everything else - legal moves, symmetries, the solver - is derived from
+/// the hole coordinates and the lattice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PegBoard {
+    lattice: Lattice,
+    coordinates: Vec<(i32, i32)>,
+    jumps: Vec<JumpMask>,
+    // symmetries[s][i] is the hole that hole i lands on under symmetry s
+    symmetries: Vec<Vec<usize>>,
+}
+
+impl PegBoard {
+    /// Builds a board from a text layout where every `o` is a hole.
+    ///
+    /// Holes are numbered row by row, left to right.
+    pub fn from_layout(layout: &[&str], lattice: Lattice) -> PegBoard {
+        let coordinates: Vec<(i32, i32)> = layout.iter()
+            .enumerate()
+            .flat_map(|(r, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, ch)| *ch == 'o')
+                    .map(move |(c, _)| (r as i32, c as i32))
+            })
+            .collect();
+
+        PegBoard::from_coordinates(coordinates, lattice)
+    }
+
+    pub fn from_coordinates(coordinates: Vec<(i32, i32)>, lattice: Lattice) -> PegBoard {
+        if coordinates.len() > MAX_HOLES {
+            panic!("a board can have at most {} holes", MAX_HOLES);
+        }
+
+        let jumps = find_jumps(&coordinates, lattice);
+        let symmetries = find_symmetries(&coordinates, &jumps, lattice);
+
+        PegBoard {
+            lattice,
+            coordinates,
+            jumps,
+            symmetries,
+        }
+    }
+
+    pub fn lattice(&self) -> Lattice {
+        self.lattice
+    }
+
+    pub fn hole_count(&self) -> usize {
+        self.coordinates.len()
+    }
+
+    pub fn coordinates(&self, hole: usize) -> (i32, i32) {
+        self.coordinates[hole]
+    }
+
+    pub fn hole_at(&self, row: i32, col: i32) -> Option<usize> {
+        self.coordinates.iter().position(|c| *c == (row, col))
+    }
+
+    pub fn jumps(&self) -> Vec<PegMove> {
+        self.jumps.iter().map(|jump| jump.action).collect()
+    }
+
+    pub fn symmetry_count(&self) -> usize {
+        self.symmetries.len()
+    }
+
+    /// Every hole filled.
+    pub fn full(&self) -> BitBoard {
+        if self.hole_count() == MAX_HOLES {
+            BitBoard::MAX
+        } else {
+            (1 << self.hole_count()) - 1
+        }
+    }
+
+    pub fn with_empty_hole(&self, empty_hole: usize) -> BitBoard {
+        self.full() & !hole_bit(empty_hole)
+    }
+
+    pub fn from_holes(&self, holes: &[bool]) -> BitBoard {
+        holes.iter()
+            .take(self.hole_count())
+            .enumerate()
+            .filter(|(_, has_peg)| **has_peg)
+            .fold(0, |acc, (hole, _)| acc | hole_bit(hole))
+    }
+
+    pub fn to_holes(&self, pegs: BitBoard) -> Vec<bool> {
+        (0..self.hole_count())
+            .map(|hole| has_peg(pegs, hole))
+            .collect()
+    }
+
+    pub fn legal_moves(&self, pegs: BitBoard) -> Vec<PegMove> {
+        self.jumps.iter()
+            .filter(|jump| jump.is_legal(pegs))
+            .map(|jump| jump.action)
+            .collect()
+    }
+
+    pub fn is_legal(&self, pegs: BitBoard, action: &PegMove) -> bool {
+        self.jumps.iter()
+            .any(|jump| jump.action == *action && jump.is_legal(pegs))
+    }
+
+    pub fn after_move(&self, pegs: BitBoard, action: &PegMove) -> BitBoard {
+        let jump = JumpMask::new(*action);
+        if !jump.is_legal(pegs) {
+            panic!("oops - this move isn't valid");
+        }
+
+        pegs ^ jump.from_over ^ jump.to
+    }
+
+    /// The moves that could have been played to reach `pegs`.
+    pub fn reverse_moves(&self, pegs: BitBoard) -> Vec<PegMove> {
+        self.jumps.iter()
+            .filter(|jump| pegs & jump.to == jump.to && pegs & jump.from_over == 0)
+            .map(|jump| jump.action)
+            .collect()
+    }
+
+    /// The position `action` was played from to reach `pegs`.
+    pub fn before_move(&self, pegs: BitBoard, action: &PegMove) -> BitBoard {
+        let jump = JumpMask::new(*action);
+        if pegs & jump.to == 0 || pegs & jump.from_over != 0 {
+            panic!("oops - this move can't be undone");
+        }
+
+        pegs ^ jump.from_over ^ jump.to
+    }
+
+    /// Indices of the symmetries that leave every one of `holes` in place.
+    pub fn symmetries_fixing(&self, holes: &[usize]) -> Vec<usize> {
+        self.symmetries.iter()
+            .enumerate()
+            .filter(|(_, permutation)| holes.iter().all(|h| permutation[*h] == *h))
+            .map(|(s, _)| s)
+            .collect()
+    }
+
+    pub fn apply_symmetry(&self, symmetry: usize, pegs: BitBoard) -> BitBoard {
+        self.symmetries[symmetry].iter()
+            .enumerate()
+            .filter(|(hole, _)| has_peg(pegs, *hole))
+            .fold(0, |acc, (_, image)| acc | hole_bit(*image))
+    }
+
+    /// The smallest image of `pegs` under the given symmetries.
+    pub fn canonical(&self, pegs: BitBoard, symmetries: &[usize]) -> BitBoard {
+        symmetries.iter()
+            .map(|s| self.apply_symmetry(*s, pegs))
+            .min()
+            .unwrap_or(pegs)
+    }
+}
+
+pub fn has_peg(pegs: BitBoard, hole: usize) -> bool {
+    pegs & hole_bit(hole) != 0
+}
+
+pub fn count_pegs(pegs: BitBoard) -> u32 {
+    pegs.count_ones()
+}
+
+fn find_jumps(coordinates: &[(i32, i32)], lattice: Lattice) -> Vec<JumpMask> {
+    let hole_at = |r: i32, c: i32| coordinates.iter().position(|x| *x == (r, c));
+
+    let mut actions: Vec<PegMove> = vec![];
+    for (from, (r, c)) in coordinates.iter().enumerate() {
+        for (dr, dc) in lattice.directions() {
+            let over = hole_at(r + dr, c + dc);
+            let to = hole_at(r + 2 * dr, c + 2 * dc);
+            if let (Some(over), Some(to)) = (over, to) {
+                actions.push(PegMove { from, over, to });
+            }
+        }
+    }
+    actions.sort_by_key(|a| (a.from, a.to));
+
+    actions.into_iter()
+        .map(JumpMask::new)
+        .collect()
+}
+
+fn find_symmetries(
+    coordinates: &[(i32, i32)],
+    jumps: &[JumpMask],
+    lattice: Lattice,
+) -> Vec<Vec<usize>> {
+    // Transforms work on the layout moved up against row 0 and column 0.
+    let min_row = coordinates.iter().map(|(r, _)| *r).min().unwrap_or(0);
+    let min_col = coordinates.iter().map(|(_, c)| *c).min().unwrap_or(0);
+    let extent = coordinates.iter()
+        .map(|(r, c)| (r - min_row).max(c - min_col))
+        .max()
+        .unwrap_or(0);
+    let hole_at = |(r, c): (i32, i32)| {
+        coordinates.iter().position(|x| *x == (r + min_row, c + min_col))
+    };
+
+    lattice.transforms(extent).iter()
+        .filter_map(|transform| {
+            coordinates.iter()
+                .map(|(r, c)| hole_at(transform(r - min_row, c - min_col)))
+                .collect::<Option<Vec<usize>>>()
+        })
+        .filter(|permutation| {
+            jumps.iter().all(|jump| {
+                let image = PegMove {
+                    from: permutation[jump.action.from],
+                    over: permutation[jump.action.over],
+                    to: permutation[jump.action.to],
+                };
+                jumps.iter().any(|j| j.action == image)
+            })
+        })
+        .collect()
+}
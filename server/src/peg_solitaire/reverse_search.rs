@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+
+use super::goal::PegGoal;
+use super::peg_board::{BitBoard, PegBoard, PegMove, count_pegs};
+
+/// Every position with at most `max_pegs` pegs from which a goal can be
+/// reached, found by searching backwards from the goal states.
+///
+/// Jumps are reversible - a peg can jump back over an empty hole and leave
+/// a peg behind - so undoing moves from the goal states visits exactly the
+/// positions that can reach them, and nothing else.
+#[derive(Debug, Clone)]
+pub struct SolvabilityTable {
+    board: PegBoard,
+    goal: PegGoal,
+    symmetries: Vec<usize>,
+    max_pegs: u32,
+    // canonical forms only
+    solvable: HashSet<BitBoard>,
+}
+
+impl SolvabilityTable {
+    pub fn build(board: &PegBoard, goal: PegGoal, max_pegs: u32) -> SolvabilityTable {
+        let symmetries = goal.symmetries(board);
+        let mut solvable: HashSet<BitBoard> = HashSet::new();
+
+        let mut layer: Vec<BitBoard> = goal.goal_states(board)
+            .into_iter()
+            .filter(|pegs| count_pegs(*pegs) <= max_pegs)
+            .map(|pegs| board.canonical(pegs, &symmetries))
+            .filter(|pegs| solvable.insert(*pegs))
+            .collect();
+
+        // each reverse move adds a peg, so layers go up one peg at a time
+        while !layer.is_empty() {
+            let mut next_layer: Vec<BitBoard> = vec![];
+
+            for pegs in layer.into_iter().filter(|pegs| count_pegs(*pegs) < max_pegs) {
+                for action in board.reverse_moves(pegs) {
+                    let previous = board.canonical(board.before_move(pegs, &action), &symmetries);
+                    if solvable.insert(previous) {
+                        next_layer.push(previous);
+                    }
+                }
+            }
+
+            layer = next_layer;
+        }
+
+        SolvabilityTable {
+            board: board.clone(),
+            goal,
+            symmetries,
+            max_pegs,
+            solvable,
+        }
+    }
+
+    pub fn board(&self) -> &PegBoard {
+        &self.board
+    }
+
+    pub fn goal(&self) -> PegGoal {
+        self.goal
+    }
+
+    pub fn max_pegs(&self) -> u32 {
+        self.max_pegs
+    }
+
+    /// The number of solvable positions, counting symmetric ones once.
+    pub fn len(&self) -> usize {
+        self.solvable.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solvable.is_empty()
+    }
+
+    pub fn covers(&self, pegs: BitBoard) -> bool {
+        count_pegs(pegs) <= self.max_pegs
+    }
+
+    /// Whether the goal can be reached from `pegs`, or `None` if the
+    /// position has more pegs than the table was built for.
+    pub fn is_solvable(&self, pegs: BitBoard) -> Option<bool> {
+        if self.covers(pegs) {
+            Some(self.solvable.contains(&self.board.canonical(pegs, &self.symmetries)))
+        } else {
+            None
+        }
+    }
+
+    /// A line of play from `pegs` to the goal, read off the table by
+    /// always stepping to another solvable position.
+    pub fn solution_from(&self, pegs: BitBoard) -> Option<Vec<PegMove>> {
+        if self.is_solvable(pegs) != Some(true) {
+            return None;
+        }
+
+        let mut moves: Vec<PegMove> = vec![];
+        let mut current = pegs;
+        while !self.goal.is_reached(&self.board, current) {
+            let next_move = self.board.legal_moves(current)
+                .into_iter()
+                .find(|action| {
+                    self.is_solvable(self.board.after_move(current, action)) == Some(true)
+                })?;
+
+            current = self.board.after_move(current, &next_move);
+            moves.push(next_move);
+        }
+
+        Some(moves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peg_solitaire::boards::ENGLISH_CENTER;
+    use crate::peg_solitaire::peg_board::hole_bit;
+    use crate::peg_solitaire::solver::solve;
+
+    #[test]
+    fn test_table_agrees_with_forward_search() {
+        let board = PegBoard::triangle();
+
+        for hole in 0..board.hole_count() {
+            let goal = PegGoal::FinishInHole(hole);
+            let table = SolvabilityTable::build(&board, goal, 14);
+            let start = board.with_empty_hole(hole);
+
+            assert_eq!(
+                table.is_solvable(start),
+                Some(solve(&board, start, goal).is_some()),
+                "starting and finishing in hole {}", hole,
+            );
+        }
+    }
+
+    #[test]
+    fn test_solution_from_table() {
+        let board = PegBoard::triangle();
+        let goal = PegGoal::FinishInHole(0);
+        let table = SolvabilityTable::build(&board, goal, 14);
+        let start = board.with_empty_hole(0);
+
+        let moves = table.solution_from(start).unwrap();
+
+        let end = moves.iter().fold(start, |pegs, action| board.after_move(pegs, action));
+        assert_eq!(end, hole_bit(0));
+        assert_eq!(moves.len(), 13);
+    }
+
+    #[test]
+    fn test_table_outside_its_peg_count() {
+        let board = PegBoard::english();
+        let table = SolvabilityTable::build(&board, PegGoal::FinishInHole(ENGLISH_CENTER), 4);
+
+        assert_eq!(table.is_solvable(board.with_empty_hole(ENGLISH_CENTER)), None);
+        assert_eq!(table.is_solvable(hole_bit(ENGLISH_CENTER)), Some(true));
+        assert_eq!(table.is_solvable(hole_bit(0)), Some(false));
+
+        // two pegs in a line leading into the center
+        let two_pegs = hole_bit(ENGLISH_CENTER - 1) | hole_bit(ENGLISH_CENTER - 2);
+        assert_eq!(table.is_solvable(two_pegs), Some(true));
+    }
+}
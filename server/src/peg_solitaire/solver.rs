@@ -0,0 +1,558 @@
+use crate::tree::TreeEvaluator;
+use crate::tree::TreeNode;
+use crate::outcomes::BinaryOutcome;
+use crate::value_cache::{InMemoryValueCache, ValueCache};
+
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+use super::goal::PegGoal;
+use super::peg_board::{BitBoard, PegBoard, PegMove, count_pegs};
+use super::reverse_search::SolvabilityTable;
+
+#[derive(Debug)]
+struct SearchContext<'a> {
+    board: &'a PegBoard,
+    goal: PegGoal,
+    table: Option<&'a SolvabilityTable>,
+    // only symmetries that keep the goal in place can share cached values
+    symmetries: Vec<usize>,
+}
+
+impl <'a> SearchContext<'a> {
+    fn new(board: &'a PegBoard, goal: PegGoal) -> SearchContext<'a> {
+        SearchContext {
+            board,
+            goal,
+            table: None,
+            symmetries: goal.symmetries(board),
+        }
+    }
+
+    fn with_table(table: &'a SolvabilityTable) -> SearchContext<'a> {
+        SearchContext {
+            table: Some(table),
+            ..SearchContext::new(table.board(), table.goal())
+        }
+    }
+
+    fn table_value(&self, pegs: BitBoard) -> Option<bool> {
+        self.table.and_then(|table| table.is_solvable(pegs))
+    }
+
+    /// Positions whose value is known without looking at their children.
+    fn is_settled(&self, pegs: BitBoard) -> bool {
+        self.goal.is_reached(self.board, pegs) ||
+            self.goal.is_out_of_reach(self.board, pegs) ||
+            self.table_value(pegs).is_some()
+    }
+
+    fn settled_value(&self, pegs: BitBoard) -> BinaryOutcome {
+        let is_win = self.goal.is_reached(self.board, pegs) ||
+            self.table_value(pegs) == Some(true);
+
+        if is_win {
+            BinaryOutcome::Win
+        } else {
+            BinaryOutcome::Lose
+        }
+    }
+}
+
+// The context is shared by every node of a search, so nodes only need to
+// compare positions.
+impl <'a> PartialEq for SearchContext<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PegNode<'a> {
+    context: &'a SearchContext<'a>,
+    pegs: BitBoard,
+    legal_moves: Vec<PegMove>,
+    move_index: usize,
+    all_children_lose: bool
+}
+
+impl <'a> PegNode<'a> {
+    fn new(context: &'a SearchContext<'a>, pegs: BitBoard) -> PegNode<'a> {
+        let legal_moves = if context.is_settled(pegs) {
+            vec![]
+        } else {
+            context.board.legal_moves(pegs)
+        };
+
+        PegNode {
+            context,
+            pegs,
+            legal_moves,
+            move_index: 0,
+            all_children_lose: true
+        }
+    }
+}
+
+impl <'a> TreeNode<PegNode<'a>, BinaryOutcome> for PegNode<'a> {
+    fn on_child_pruned(&mut self, _child: PegNode<'a>, child_value: BinaryOutcome) {
+        self.all_children_lose = self.all_children_lose && child_value == BinaryOutcome::Lose;
+        self.move_index += 1;
+    }
+
+    fn request_next_child(&mut self) -> Option<PegNode<'a>> {
+        if self.move_index < self.legal_moves.len() {
+            let current_move = self.legal_moves[self.move_index];
+            let next_pegs = self.context.board.after_move(self.pegs, &current_move);
+
+            Some(PegNode::new(self.context, next_pegs))
+        } else {
+            None
+        }
+    }
+
+    fn on_all_children_pruned(&mut self) -> BinaryOutcome {
+        if self.legal_moves.is_empty() {
+            self.context.settled_value(self.pegs)
+        } else if !self.all_children_lose {
+            BinaryOutcome::Win
+        } else {
+            BinaryOutcome::Lose
+        }
+    }
+}
+
+fn get_key_for_node(node: &PegNode) -> BitBoard {
+    node.context.board.canonical(node.pegs, &node.context.symmetries)
+}
+
+/// Finds a sequence of jumps from `start` that reaches `goal`, or `None`
+/// if there isn't one.
+pub fn solve(
+    board: &PegBoard,
+    start: BitBoard,
+    goal: PegGoal,
+) -> Option<Vec<PegMove>> {
+    let context = SearchContext::new(board, goal);
+
+    search_for_win(&context, start, InMemoryValueCache::new(get_key_for_node), None)
+        .expect("searches without a deadline always finish")
+}
+
+/// Like `solve`, but the search stops as soon as it reaches a position the
+/// table covers and finishes the line from the table.
+pub fn solve_with_table(
+    table: &SolvabilityTable,
+    start: BitBoard,
+) -> Option<Vec<PegMove>> {
+    let context = SearchContext::with_table(table);
+
+    search_for_win(&context, start, InMemoryValueCache::new(get_key_for_node), None)
+        .expect("searches without a deadline always finish")
+}
+
+/// A search that ran past its deadline before it could finish.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TimedOut;
+
+fn search_for_win<'a, C>(
+    context: &'a SearchContext<'a>,
+    start: BitBoard,
+    node_value_cache: C,
+    deadline: Option<Instant>,
+) -> Result<Option<Vec<PegMove>>, TimedOut>
+    where C: ValueCache<PegNode<'a>, BinaryOutcome> + Debug {
+    if context.goal.is_reached(context.board, start) {
+        return Ok(Some(vec![]));
+    }
+    if let Some(table) = context.table.filter(|table| table.covers(start)) {
+        return Ok(table.solution_from(start));
+    }
+
+    let mut tree = TreeEvaluator::new(
+        PegNode::new(context, start),
+        node_value_cache,
+        context.board.hole_count(),
+        Some(BinaryOutcome::Win)
+    );
+
+    if !tree.search_until(deadline) {
+        return Err(TimedOut);
+    }
+
+    let root = tree.root_node();
+    if root.move_index < root.legal_moves.len() {
+        let mut moves: Vec<PegMove> = tree.get_tree_path().iter().map(|x| {
+            x.legal_moves[x.move_index]
+        }).collect();
+
+        let end = moves.iter()
+            .fold(start, |pegs, action| context.board.after_move(pegs, action));
+        if let Some(table) = context.table.filter(|_| !context.goal.is_reached(context.board, end)) {
+            match table.solution_from(end) {
+                Some(rest) => moves.extend(rest),
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(moves))
+    } else {
+        Ok(None)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionEvaluation {
+    /// `None` if the search ran out of time before it could tell.
+    pub is_solvable: Option<bool>,
+    /// `None` if the search ran out of time before it could tell.
+    pub min_reachable_pegs: Option<u32>,
+    /// The legal moves after which a win is still possible. If the search
+    /// ran out of time, only the ones it got as far as proving.
+    pub winning_moves: Vec<PegMove>,
+}
+
+/// Evaluates a position for hints: whether `goal` can still be reached,
+/// the fewest pegs it can be reduced to, and which of its legal moves keep
+/// the goal reachable.
+///
+/// Hard positions on the larger boards can take far longer than a caller
+/// will wait, so the searches give up after `time_limit` and leave what
+/// they couldn't work out undetermined.
+pub fn evaluate_position(
+    board: &PegBoard,
+    pegs: BitBoard,
+    goal: PegGoal,
+    time_limit: Option<Duration>,
+) -> PositionEvaluation {
+    let deadline = time_limit.map(|limit| Instant::now() + limit);
+    let context = SearchContext::new(board, goal);
+    // every move is searched against the same goal, so losing positions
+    // found under one move are shared with the others
+    let mut node_value_cache = InMemoryValueCache::new(get_key_for_node);
+
+    let mut winning_moves: Vec<PegMove> = vec![];
+    let mut timed_out = false;
+    for action in board.legal_moves(pegs) {
+        let next_pegs = board.after_move(pegs, &action);
+        match search_for_win(&context, next_pegs, &mut node_value_cache, deadline) {
+            Ok(Some(_)) => winning_moves.push(action),
+            Ok(None) => {},
+            Err(TimedOut) => {
+                timed_out = true;
+                break;
+            },
+        }
+    }
+
+    let is_solvable = if goal.is_reached(board, pegs) || !winning_moves.is_empty() {
+        Some(true)
+    } else if timed_out {
+        None
+    } else {
+        Some(false)
+    };
+    // a reachable single peg goal is as few pegs as there can be; other
+    // goals may stop short of that
+    let min_reachable_pegs = if is_solvable == Some(true) && goal.peg_count() == 1 {
+        Some(1)
+    } else {
+        min_reachable_pegs_until(board, pegs, deadline).ok()
+    };
+
+    PositionEvaluation {
+        is_solvable,
+        min_reachable_pegs,
+        winning_moves,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct MinPegsNode<'a> {
+    context: &'a SearchContext<'a>,
+    pegs: BitBoard,
+    legal_moves: Vec<PegMove>,
+    move_index: usize,
+    min_child_pegs: u32
+}
+
+impl <'a> MinPegsNode<'a> {
+    fn new(context: &'a SearchContext<'a>, pegs: BitBoard) -> MinPegsNode<'a> {
+        MinPegsNode {
+            context,
+            pegs,
+            legal_moves: context.board.legal_moves(pegs),
+            move_index: 0,
+            min_child_pegs: u32::MAX
+        }
+    }
+}
+
+impl <'a> TreeNode<MinPegsNode<'a>, u32> for MinPegsNode<'a> {
+    fn on_child_pruned(&mut self, _child: MinPegsNode<'a>, child_value: u32) {
+        self.min_child_pegs = self.min_child_pegs.min(child_value);
+        self.move_index += 1;
+    }
+
+    fn request_next_child(&mut self) -> Option<MinPegsNode<'a>> {
+        if self.move_index < self.legal_moves.len() {
+            let current_move = self.legal_moves[self.move_index];
+            let next_pegs = self.context.board.after_move(self.pegs, &current_move);
+
+            Some(MinPegsNode::new(self.context, next_pegs))
+        } else {
+            None
+        }
+    }
+
+    fn on_all_children_pruned(&mut self) -> u32 {
+        if self.legal_moves.is_empty() {
+            count_pegs(self.pegs)
+        } else {
+            self.min_child_pegs
+        }
+    }
+}
+
+fn get_key_for_min_pegs_node(node: &MinPegsNode) -> BitBoard {
+    node.context.board.canonical(node.pegs, &node.context.symmetries)
+}
+
+/// The fewest pegs that can be left on the board by playing on from `pegs`.
+pub fn min_reachable_pegs(board: &PegBoard, pegs: BitBoard) -> u32 {
+    min_reachable_pegs_until(board, pegs, None)
+        .expect("searches without a deadline always finish")
+}
+
+fn min_reachable_pegs_until(
+    board: &PegBoard,
+    pegs: BitBoard,
+    deadline: Option<Instant>,
+) -> Result<u32, TimedOut> {
+    if count_pegs(pegs) <= 1 {
+        return Ok(count_pegs(pegs));
+    }
+
+    let context = SearchContext::new(board, PegGoal::SinglePeg);
+    let node_value_cache = InMemoryValueCache::new(get_key_for_min_pegs_node);
+
+    let mut tree = TreeEvaluator::new(
+        MinPegsNode::new(&context, pegs),
+        node_value_cache,
+        board.hole_count(),
+        Some(1)
+    );
+
+    if !tree.search_until(deadline) {
+        return Err(TimedOut);
+    }
+
+    let root = tree.root_node();
+    if root.move_index < root.legal_moves.len() {
+        // the search only stops early once a single peg is reached
+        Ok(1)
+    } else {
+        Ok(root.clone().on_all_children_pruned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peg_solitaire::boards::{ENGLISH_CENTER, WIEGLEB_CENTER};
+
+    fn replay(board: &PegBoard, start: BitBoard, moves: &[PegMove]) -> BitBoard {
+        moves.iter().fold(start, |pegs, action| {
+            assert!(board.is_legal(pegs, action));
+            board.after_move(pegs, action)
+        })
+    }
+
+    #[test]
+    fn test_triangle_peg_search() {
+        let board = PegBoard::triangle();
+        let start = board.with_empty_hole(12);
+
+        let action_list = solve(&board, start, PegGoal::SinglePeg).unwrap();
+
+        let expected_action_list = vec![
+            PegMove { from: 3, over: 7, to: 12 },
+            PegMove { from: 0, over: 1, to: 3 },
+            PegMove { from: 2, over: 4, to: 7 },
+            PegMove { from: 6, over: 3, to: 1 },
+            PegMove { from: 9, over: 5, to: 2 },
+            PegMove { from: 11, over: 7, to: 4 },
+            PegMove { from: 12, over: 8, to: 5 },
+            PegMove { from: 1, over: 4, to: 8 },
+            PegMove { from: 2, over: 5, to: 9 },
+            PegMove { from: 14, over: 9, to: 5 },
+            PegMove { from: 5, over: 8, to: 12 },
+            PegMove { from: 13, over: 12, to: 11 },
+            PegMove { from: 10, over: 11, to: 12 }
+        ];
+
+        assert_eq!(expected_action_list, action_list);
+        assert_eq!(count_pegs(replay(&board, start, &action_list)), 1);
+    }
+
+    #[test]
+    fn test_triangle_target_hole() {
+        let board = PegBoard::triangle();
+        let start = board.with_empty_hole(0);
+
+        let action_list = solve(&board, start, PegGoal::FinishInHole(0)).unwrap();
+
+        assert_eq!(replay(&board, start, &action_list), 1 << 0);
+    }
+
+    #[test]
+    fn test_english_peg_search() {
+        let board = PegBoard::english();
+        let start = board.with_empty_hole(ENGLISH_CENTER);
+
+        let action_list = solve(&board, start, PegGoal::FinishInHole(ENGLISH_CENTER)).unwrap();
+
+        assert_eq!(action_list.len(), 31);
+        assert_eq!(replay(&board, start, &action_list), 1 << ENGLISH_CENTER);
+    }
+
+    #[test]
+    fn test_unsolvable_position() {
+        let board = PegBoard::english();
+        let mut holes = vec![false; board.hole_count()];
+        holes[0] = true;
+        holes[32] = true;
+
+        assert_eq!(solve(&board, board.from_holes(&holes), PegGoal::SinglePeg), None);
+    }
+
+    #[test]
+    fn test_min_reachable_pegs() {
+        let board = PegBoard::english();
+
+        let mut holes = vec![false; board.hole_count()];
+        holes[0] = true;
+        holes[32] = true;
+        assert_eq!(min_reachable_pegs(&board, board.from_holes(&holes)), 2);
+
+        holes[3] = true;
+        assert_eq!(min_reachable_pegs(&board, board.from_holes(&holes)), 2);
+
+        assert_eq!(min_reachable_pegs(&board, board.with_empty_hole(ENGLISH_CENTER)), 1);
+    }
+
+    #[test]
+    fn test_evaluate_position() {
+        let board = PegBoard::triangle();
+        let pegs = board.with_empty_hole(0);
+
+        let evaluation = evaluate_position(&board, pegs, PegGoal::SinglePeg, None);
+
+        assert_eq!(evaluation.is_solvable, Some(true));
+        assert_eq!(evaluation.min_reachable_pegs, Some(1));
+        assert_eq!(evaluation.winning_moves, vec![
+            PegMove { from: 3, over: 1, to: 0 },
+            PegMove { from: 5, over: 2, to: 0 },
+        ]);
+        for action in evaluation.winning_moves {
+            assert!(solve(&board, board.after_move(pegs, &action), PegGoal::SinglePeg).is_some());
+        }
+    }
+
+    #[test]
+    fn test_evaluate_position_with_target_hole() {
+        let board = PegBoard::english();
+        let mut holes = vec![false; board.hole_count()];
+        // two pegs that can only finish away from the center
+        holes[0] = true;
+        holes[1] = true;
+        let pegs = board.from_holes(&holes);
+
+        let to_center = evaluate_position(&board, pegs, PegGoal::FinishInHole(ENGLISH_CENTER), None);
+        assert_eq!(to_center.is_solvable, Some(false));
+        assert_eq!(to_center.min_reachable_pegs, Some(1));
+        assert_eq!(to_center.winning_moves, vec![]);
+
+        let anywhere = evaluate_position(&board, pegs, PegGoal::SinglePeg, None);
+        assert_eq!(anywhere.is_solvable, Some(true));
+        assert_eq!(anywhere.winning_moves, vec![
+            PegMove { from: 0, over: 1, to: 2 },
+        ]);
+    }
+
+    #[test]
+    fn test_evaluate_position_with_multi_peg_goals() {
+        let board = PegBoard::triangle();
+        let mut holes = vec![false; board.hole_count()];
+        // two corners, with no jumps between them
+        holes[0] = true;
+        holes[14] = true;
+
+        let evaluation = evaluate_position(&board, board.from_holes(&holes), PegGoal::LeaveExactly(2), None);
+        assert_eq!(evaluation.is_solvable, Some(true));
+        assert_eq!(evaluation.min_reachable_pegs, Some(2));
+        assert_eq!(evaluation.winning_moves, vec![]);
+
+        let pair = (1 << 10) | (1 << 11);
+        let evaluation = evaluate_position(&board, board.with_empty_hole(12), PegGoal::Pattern(pair), None);
+        assert_eq!(evaluation.is_solvable, Some(true));
+        assert_eq!(evaluation.min_reachable_pegs, Some(1));
+        assert!(!evaluation.winning_moves.is_empty());
+    }
+
+    #[test]
+    fn test_wiegleb_endgame() {
+        let board = PegBoard::wiegleb();
+        let mut holes = vec![false; board.hole_count()];
+        let left_of_center = WIEGLEB_CENTER - 1;
+        let two_left_of_center = WIEGLEB_CENTER - 2;
+        holes[two_left_of_center] = true;
+        holes[left_of_center] = true;
+
+        let action_list = solve(&board, board.from_holes(&holes), PegGoal::FinishInHole(WIEGLEB_CENTER)).unwrap();
+
+        assert_eq!(action_list, vec![PegMove { from: two_left_of_center, over: left_of_center, to: WIEGLEB_CENTER }]);
+    }
+
+    #[test]
+    fn test_leave_exactly() {
+        let board = PegBoard::triangle();
+        let start = board.with_empty_hole(0);
+
+        let action_list = solve(&board, start, PegGoal::LeaveExactly(3)).unwrap();
+
+        let end = replay(&board, start, &action_list);
+        assert_eq!(count_pegs(end), 3);
+        assert!(board.legal_moves(end).is_empty());
+    }
+
+    #[test]
+    fn test_pattern_goal() {
+        let board = PegBoard::triangle();
+        let start = board.with_empty_hole(12);
+        // the corner and its neighbor along the bottom row
+        let pair = (1 << 10) | (1 << 11);
+
+        let action_list = solve(&board, start, PegGoal::Pattern(pair)).unwrap();
+
+        assert_eq!(action_list.len(), 12);
+        assert_eq!(replay(&board, start, &action_list), pair);
+
+        // three corners can't be reached from a single empty corner
+        let corners = (1 << 0) | (1 << 10) | (1 << 14);
+        assert_eq!(solve(&board, board.with_empty_hole(0), PegGoal::Pattern(corners)), None);
+    }
+
+    #[test]
+    fn test_solve_with_table() {
+        let board = PegBoard::english();
+        let goal = PegGoal::FinishInHole(ENGLISH_CENTER);
+        let table = SolvabilityTable::build(&board, goal, 8);
+        let start = board.with_empty_hole(ENGLISH_CENTER);
+
+        let action_list = solve_with_table(&table, start).unwrap();
+
+        assert_eq!(action_list.len(), 31);
+        assert_eq!(replay(&board, start, &action_list), 1 << ENGLISH_CENTER);
+    }
+}
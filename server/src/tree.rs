@@ -1,7 +1,8 @@
 use std::fmt::Debug;
+use std::time::Instant;
 
-use crate::node_value_cache::NodeValueCache;
-use crate::node_value_cache::NoOpCache;
+use crate::value_cache::ValueCache;
+use crate::value_cache::NoOpCache;
 
 pub trait TreeNode<T,V>
     where T: TreeNode<T,V> + Debug + Clone + PartialEq,
@@ -15,7 +16,7 @@ pub trait TreeNode<T,V>
 pub struct TreeEvaluator<T,V,C>
     where T: TreeNode<T,V> + Debug + Clone + PartialEq,
           V: PartialEq + Debug + Clone,
-          C: NodeValueCache<T,V> + Debug {
+          C: ValueCache<T,V> + Debug {
 
     tree_path: Vec<T>,
     node_value_cache: C,
@@ -27,7 +28,7 @@ pub struct TreeEvaluator<T,V,C>
 impl <T,V,C> TreeEvaluator<T,V,C>
     where T: TreeNode<T,V> + Debug + Clone + PartialEq,
           V: PartialEq + Debug + Clone,
-          C: NodeValueCache<T,V> + Debug {
+          C: ValueCache<T,V> + Debug {
 
     pub fn new(
         root_node: T,
@@ -75,6 +76,22 @@ impl <T,V,C> TreeEvaluator<T,V,C>
         }
     }
 
+    /// Like `search`, but gives up once `deadline` has passed. Returns
+    /// whether the search finished.
+    pub fn search_until(&mut self, deadline: Option<Instant>) -> bool {
+        let mut nodes_visited: u32 = 0;
+        while !self.is_finished {
+            self.next();
+            nodes_visited = nodes_visited.wrapping_add(1);
+
+            // checking the clock is slow next to visiting a node
+            if nodes_visited.is_multiple_of(1024) && deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return false;
+            }
+        }
+        true
+    }
+
     pub fn search_with_max_visits(&mut self, max_node_visits: u32, node_fmt: fn(&T) -> String) {
         let mut nodes_visited = 0;
         let mut last_tree_path = self.tree_path.clone();
@@ -105,11 +122,11 @@ impl <T,V,C> TreeEvaluator<T,V,C>
         if path_length <= self.max_depth {
             match tail_node.request_next_child() {
                 Some(child) => {
-                    let maybe_already_evaluated = self.node_value_cache.get_value(&tail_node);
+                    let maybe_already_evaluated = self.node_value_cache.get(&child);
                     match maybe_already_evaluated {
                         Some(value) => {
                             // println!("already evaluated, not branching");
-                            tail_node.on_child_pruned(child, value.clone());
+                            tail_node.on_child_pruned(child, value);
                         }
                         None => {
                             // println!("not evaluated, branching");
@@ -140,8 +157,8 @@ impl <T,V,C> TreeEvaluator<T,V,C>
             if self.should_stop_early(&node_value) {
                 self.is_finished = true;
             } else {
-                if let None = self.node_value_cache.get_value(&node_to_prune) {
-                    self.node_value_cache.save_value(&node_to_prune, node_value.clone());
+                if let None = self.node_value_cache.get(&node_to_prune) {
+                    self.node_value_cache.put(&node_to_prune, node_value.clone());
                 }
                 
                 self.tree_path.last_mut().unwrap().on_child_pruned(node_to_prune, node_value);
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::fmt::Debug;
+use std::sync::{Arc, RwLock};
+
+/// Caches values computed for tree nodes or dyn_prog states.
+///
+/// `T` is whatever the cache is looked up by, `V` is the cached value.
+/// Values are returned by copy so that implementations backed by a lock
+/// don't have to hand out references into guarded storage.
+pub trait ValueCache<T,V>
+    where V: Clone {
+    fn put(&mut self, item: &T, value: V);
+    fn get(&self, item: &T) -> Option<V>;
+    fn size(&self) -> usize;
+}
+
+/// Lets several searches in a row fill and read the same cache.
+impl <T,V,C> ValueCache<T,V> for &mut C
+    where V: Clone,
+          C: ValueCache<T,V> {
+    fn put(&mut self, item: &T, value: V) { (**self).put(item, value) }
+    fn get(&self, item: &T) -> Option<V> { (**self).get(item) }
+    fn size(&self) -> usize { (**self).size() }
+}
+
+#[derive(Debug)]
+pub struct NoOpCache {}
+
+impl <T,V> ValueCache<T,V> for NoOpCache where V: Clone {
+    fn put(&mut self, _item: &T, _value: V) {}
+    fn get(&self, _item: &T) -> Option<V> { None }
+    fn size(&self) -> usize { 0 }
+}
+
+pub struct InMemoryValueCache<T,K,V>
+    where K: Eq + Hash + Debug,
+          V: Debug {
+    value_map: HashMap<K,V>,
+    get_key: fn(&T) -> K
+}
+
+impl <T,K,V> Debug for InMemoryValueCache<T,K,V>
+    where K: Eq + Hash + Debug,
+          V: Debug {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.value_map.fmt(formatter)
+    }
+}
+
+impl <T,K,V> InMemoryValueCache<T,K,V>
+    where K: Eq + Hash + Debug,
+          V: Debug {
+    pub fn new(get_key: fn(&T) -> K) -> InMemoryValueCache<T,K,V> {
+        InMemoryValueCache {
+            value_map: HashMap::new(),
+            get_key
+        }
+    }
+}
+
+impl <T,K,V> ValueCache<T,V> for InMemoryValueCache<T,K,V>
+    where K: Eq + Hash + Debug,
+          V: Debug + Clone {
+
+    fn put(&mut self, item: &T, value: V) {
+        let key = (self.get_key)(item);
+        self.value_map.entry(key).or_insert(value);
+    }
+
+    fn get(&self, item: &T) -> Option<V> {
+        let key = (self.get_key)(item);
+        self.value_map.get(&key).cloned()
+    }
+
+    fn size(&self) -> usize {
+        self.value_map.len()
+    }
+}
+
+const DEFAULT_SHARD_COUNT: usize = 16;
+
+/// A cache that can be shared between threads.
+///
+/// Cloning hands out another handle to the same storage, so each worker
+/// of a parallel search can own its own handle.  Keys are spread over
+/// several independently locked shards to keep contention down.
+pub struct ConcurrentValueCache<T,K,V>
+    where K: Eq + Hash + Debug,
+          V: Debug {
+    shards: Arc<Vec<RwLock<HashMap<K,V>>>>,
+    get_key: fn(&T) -> K
+}
+
+impl <T,K,V> Clone for ConcurrentValueCache<T,K,V>
+    where K: Eq + Hash + Debug,
+          V: Debug {
+    fn clone(&self) -> Self {
+        ConcurrentValueCache {
+            shards: Arc::clone(&self.shards),
+            get_key: self.get_key
+        }
+    }
+}
+
+impl <T,K,V> Debug for ConcurrentValueCache<T,K,V>
+    where K: Eq + Hash + Debug,
+          V: Debug {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut map = formatter.debug_map();
+        for shard in self.shards.iter() {
+            let guard = shard.read().unwrap();
+            map.entries(guard.iter());
+        }
+        map.finish()
+    }
+}
+
+impl <T,K,V> ConcurrentValueCache<T,K,V>
+    where K: Eq + Hash + Debug,
+          V: Debug {
+    pub fn new(get_key: fn(&T) -> K) -> ConcurrentValueCache<T,K,V> {
+        ConcurrentValueCache::with_shards(get_key, DEFAULT_SHARD_COUNT)
+    }
+
+    pub fn with_shards(get_key: fn(&T) -> K, shard_count: usize) -> ConcurrentValueCache<T,K,V> {
+        let shards = (0..shard_count.max(1))
+            .map(|_| RwLock::new(HashMap::new()))
+            .collect();
+
+        ConcurrentValueCache {
+            shards: Arc::new(shards),
+            get_key
+        }
+    }
+
+    fn shard_for(&self, key: &K) -> &RwLock<HashMap<K,V>> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let index = (hasher.finish() as usize) % self.shards.len();
+
+        &self.shards[index]
+    }
+}
+
+impl <T,K,V> ValueCache<T,V> for ConcurrentValueCache<T,K,V>
+    where K: Eq + Hash + Debug,
+          V: Debug + Clone {
+
+    fn put(&mut self, item: &T, value: V) {
+        let key = (self.get_key)(item);
+        let mut shard = self.shard_for(&key).write().unwrap();
+        shard.entry(key).or_insert(value);
+    }
+
+    fn get(&self, item: &T) -> Option<V> {
+        let key = (self.get_key)(item);
+        let shard = self.shard_for(&key).read().unwrap();
+        shard.get(&key).cloned()
+    }
+
+    fn size(&self) -> usize {
+        self.shards.iter()
+            .map(|shard| shard.read().unwrap().len())
+            .sum()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    struct DummyNode {
+        id: u32,
+    }
+
+    fn dummy_get_key(node: &DummyNode) -> u32 {
+        node.id
+    }
+
+    #[test]
+    fn test_in_memory_value_cache() {
+        let mut cache = InMemoryValueCache::<DummyNode, u32, f64>::new(dummy_get_key);
+
+        let (node,v) = (DummyNode { id: 1 }, 0.6);
+
+        let expected_value = None;
+        let actual_value = cache.get(&node);
+        assert_eq!(expected_value, actual_value);
+
+        cache.put(&node, v);
+
+        let expected_value_after_save = Some(0.6);
+        let actual_value_after_save = cache.get(&node);
+        assert_eq!(expected_value_after_save, actual_value_after_save);
+    }
+
+    #[test]
+    fn test_in_memory_value_cache_keeps_first_value() {
+        let mut cache = InMemoryValueCache::<DummyNode, u32, f64>::new(dummy_get_key);
+
+        let node = DummyNode { id: 1 };
+        cache.put(&node, 0.6);
+        cache.put(&node, 0.9);
+
+        assert_eq!(cache.get(&node), Some(0.6));
+        assert_eq!(cache.size(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_value_cache_shared_between_threads() {
+        let cache = ConcurrentValueCache::<DummyNode, u32, u32>::new(dummy_get_key);
+
+        let workers: Vec<thread::JoinHandle<()>> = (0..4)
+            .map(|w| {
+                let mut handle = cache.clone();
+                thread::spawn(move || {
+                    for id in (w * 100)..((w + 1) * 100) {
+                        handle.put(&DummyNode { id }, id * 2);
+                    }
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        assert_eq!(cache.size(), 400);
+        assert_eq!(cache.get(&DummyNode { id: 0 }), Some(0));
+        assert_eq!(cache.get(&DummyNode { id: 399 }), Some(798));
+        assert_eq!(cache.get(&DummyNode { id: 400 }), None);
+    }
+}
@@ -0,0 +1,290 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::games::boggle_like::read_word_list;
+
+use super::guess_ranker::{GuessRanker, GuessSuggestion, RankingStrategy};
+use super::query::{QueryParseError, parse_query_for_length};
+use super::word_index::{MAX_WORD_LENGTH, MIN_WORD_LENGTH, WordIndexN};
+use super::wordle::{GuessResult, WORD_LENGTH, WordleError, WordleSolver};
+
+#[derive(Debug)]
+pub enum WordAnalysisError {
+    NoDictionaries,
+    UnknownDictionary(String),
+    UnsupportedLength(usize),
+    InvalidQuery(QueryParseError),
+    NoWordleAnswers,
+    Wordle(WordleError),
+}
+
+impl fmt::Display for WordAnalysisError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WordAnalysisError::NoDictionaries => {
+                write!(formatter, "no dictionaries are loaded")
+            },
+            WordAnalysisError::UnknownDictionary(name) => {
+                write!(formatter, "unknown dictionary '{}'", name)
+            },
+            WordAnalysisError::UnsupportedLength(length) => {
+                write!(
+                    formatter,
+                    "can't query {} letter words, only {} to {}",
+                    length,
+                    MIN_WORD_LENGTH,
+                    MAX_WORD_LENGTH,
+                )
+            },
+            WordAnalysisError::InvalidQuery(e) => {
+                write!(formatter, "invalid query: {}", e)
+            },
+            WordAnalysisError::NoWordleAnswers => {
+                write!(formatter, "no Wordle answer list is loaded")
+            },
+            WordAnalysisError::Wordle(e) => {
+                write!(formatter, "{}", e)
+            },
+        }
+    }
+}
+
+/// Runs word index queries against a set of named dictionaries, with an
+/// index for each word length built up front.
+#[derive(Debug)]
+pub struct WordAnalyst {
+    // sorted by name, with one index per length from MIN_WORD_LENGTH
+    dictionaries: Vec<(String, Vec<WordIndexN>)>,
+}
+
+impl WordAnalyst {
+    /// Words that aren't `MIN_WORD_LENGTH` to `MAX_WORD_LENGTH` letters from
+    /// A to Z are left out.
+    pub fn new(dictionaries: Vec<(String, Vec<String>)>) -> WordAnalyst {
+        let mut dictionaries: Vec<(String, Vec<WordIndexN>)> = dictionaries.into_iter()
+            .map(|(name, words)| {
+                let indexes = (MIN_WORD_LENGTH..=MAX_WORD_LENGTH)
+                    .map(|length| {
+                        let words_of_length = words.iter()
+                            .filter(|word| word.len() == length && word.bytes().all(|b| b.is_ascii_alphabetic()))
+                            .cloned()
+                            .collect();
+                        WordIndexN::new(length, words_of_length).expect("words were checked above")
+                    })
+                    .collect();
+                (name, indexes)
+            })
+            .collect();
+        dictionaries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        WordAnalyst {
+            dictionaries,
+        }
+    }
+
+    /// Loads every `.txt` word list in `directory`, named after its file
+    /// name without the extension.
+    pub fn load<P>(directory: P) -> io::Result<WordAnalyst>
+        where P: AsRef<Path>,
+    {
+        let mut dictionaries: Vec<(String, Vec<String>)> = vec![];
+
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|extension| extension != "txt") {
+                continue;
+            }
+
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                println!("indexing dictionary '{}'...", name);
+                dictionaries.push((name.to_owned(), read_word_list(&path)?));
+            }
+        }
+
+        Ok(WordAnalyst::new(dictionaries))
+    }
+
+    pub fn dictionary_names(&self) -> Vec<&str> {
+        self.dictionaries.iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// The `length` letter words matching `query` in the named dictionary,
+    /// or the first one by name if none is given, in word list order.
+    pub fn query(
+        &self,
+        query: &str,
+        length: usize,
+        dictionary_name: Option<&str>,
+    ) -> Result<Vec<String>, WordAnalysisError> {
+        if !(MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&length) {
+            return Err(WordAnalysisError::UnsupportedLength(length));
+        }
+        // slots past the end of the word are rejected here, with where they are
+        let parsed = parse_query_for_length(query, length).map_err(WordAnalysisError::InvalidQuery)?;
+
+        let (_, indexes) = match dictionary_name {
+            Some(name) => self.dictionaries.iter()
+                .find(|(dictionary_name, _)| dictionary_name == name)
+                .ok_or_else(|| WordAnalysisError::UnknownDictionary(name.to_owned()))?,
+            None => self.dictionaries.first()
+                .ok_or(WordAnalysisError::NoDictionaries)?,
+        };
+
+        Ok(indexes[length - MIN_WORD_LENGTH].evaluate_query(parsed))
+    }
+}
+
+/// Works out Wordle candidates and next guesses from one answer list.
+#[derive(Debug)]
+pub struct WordleAnalyst {
+    solver: WordleSolver,
+    ranker: GuessRanker,
+}
+
+impl WordleAnalyst {
+    /// See `GuessRanker::new` for `extra_guesses`.
+    pub fn new(answers: Vec<String>, extra_guesses: &[String]) -> Result<WordleAnalyst, WordleError> {
+        let solver = WordleSolver::new(answers)?;
+        let ranker = GuessRanker::new(solver.answers(), extra_guesses)?;
+
+        Ok(WordleAnalyst {
+            solver,
+            ranker,
+        })
+    }
+
+    /// Loads the five letter words of the answer list and, if there is one,
+    /// the list of other allowed guesses.
+    pub fn load<P>(answers_path: P, guesses_path: Option<P>) -> io::Result<WordleAnalyst>
+        where P: AsRef<Path>,
+    {
+        let answers = five_letter_words(read_word_list(answers_path)?);
+        let extra_guesses = match guesses_path {
+            Some(path) => five_letter_words(read_word_list(path)?),
+            None => vec![],
+        };
+
+        Ok(WordleAnalyst::new(answers, &extra_guesses).expect("words were checked above"))
+    }
+
+    pub fn answer_count(&self) -> usize {
+        self.solver.answers().len()
+    }
+
+    /// The answers that fit `history`, which is pairs of guesses and their
+    /// feedback as `GuessResult::parse` reads them.
+    pub fn candidates(&self, history: &[(String, String)]) -> Result<Vec<String>, WordAnalysisError> {
+        if self.answer_count() == 0 {
+            return Err(WordAnalysisError::NoWordleAnswers);
+        }
+
+        let history = history.iter()
+            .map(|(guess, feedback)| GuessResult::parse(guess, feedback))
+            .collect::<Result<Vec<GuessResult>, WordleError>>()
+            .map_err(WordAnalysisError::Wordle)?;
+
+        Ok(self.solver.candidates(&history))
+    }
+
+    /// The `count` best next guesses after `history`, along with how many
+    /// candidates are left.
+    pub fn suggest(
+        &self,
+        history: &[(String, String)],
+        strategy: RankingStrategy,
+        count: usize,
+    ) -> Result<(usize, Vec<GuessSuggestion>), WordAnalysisError> {
+        let candidates = self.candidates(history)?;
+        let suggestions = self.ranker.suggest(&candidates, strategy, count)
+            .map_err(WordAnalysisError::Wordle)?;
+
+        Ok((candidates.len(), suggestions))
+    }
+}
+
+fn five_letter_words(words: Vec<String>) -> Vec<String> {
+    words.into_iter()
+        .filter(|word| word.len() == WORD_LENGTH)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn history(results: &[(&str, &str)]) -> Vec<(String, String)> {
+        results.iter()
+            .map(|(guess, feedback)| (guess.to_string(), feedback.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_query() {
+        let analyst = WordAnalyst::new(vec![
+            ("small".to_owned(), to_strings(&["SHALT", "SHAFT", "SLANT", "SHAH"])),
+            ("large".to_owned(), to_strings(&["SHALT", "SHAFT", "SLANT", "SMART", "BASH", "SASH", "IT'S"])),
+        ]);
+
+        assert_eq!(analyst.dictionary_names(), vec!["large", "small"]);
+        assert_eq!(analyst.query("s1 & t5", 5, Some("small")).unwrap(), vec!["SHALT", "SHAFT", "SLANT"]);
+        assert_eq!(analyst.query("s1 & t5 & !h", 5, None).unwrap(), vec!["SLANT", "SMART"]);
+        assert_eq!(analyst.query("h4", 4, None).unwrap(), vec!["BASH", "SASH"]);
+
+        assert!(matches!(
+            analyst.query("h4", 3, None),
+            Err(WordAnalysisError::UnsupportedLength(3)),
+        ));
+        assert!(matches!(
+            analyst.query("h4", 4, Some("huge")),
+            Err(WordAnalysisError::UnknownDictionary(_)),
+        ));
+        assert_eq!(
+            analyst.query("s1 &", 5, None).unwrap_err().to_string(),
+            "invalid query: the query ends too soon at position 4",
+        );
+        match analyst.query("a7", 5, None) {
+            Err(WordAnalysisError::InvalidQuery(e)) => assert_eq!(e.position, 1),
+            other => panic!("expected an invalid query, got {:?}", other),
+        }
+        assert_eq!(
+            analyst.query("s1 & h5", 4, None).unwrap_err().to_string(),
+            "invalid query: slot 5 is not between 1 and 4 at position 6",
+        );
+        assert!(matches!(
+            WordAnalyst::new(vec![]).query("a", 5, None),
+            Err(WordAnalysisError::NoDictionaries),
+        ));
+    }
+
+    #[test]
+    fn test_wordle() {
+        let analyst = WordleAnalyst::new(
+            to_strings(&["BATCH", "CATCH", "HATCH", "LATCH", "MATCH", "CRANE"]),
+            &to_strings(&["BLIMP"]),
+        ).unwrap();
+
+        let after_crane = history(&[("crane", "y.y..")]);
+        assert_eq!(analyst.candidates(&after_crane).unwrap(), vec!["BATCH", "HATCH", "LATCH", "MATCH"]);
+
+        let (candidate_count, suggestions) = analyst.suggest(&after_crane, RankingStrategy::Entropy, 1).unwrap();
+        assert_eq!(candidate_count, 4);
+        assert_eq!(suggestions[0].guess, "BLIMP");
+
+        assert_eq!(
+            analyst.candidates(&history(&[("crane", "gyg")])).unwrap_err().to_string(),
+            WordleError::InvalidFeedback("gyg".to_owned()).to_string(),
+        );
+        assert!(matches!(
+            WordleAnalyst::new(vec![], &[]).unwrap().candidates(&[]),
+            Err(WordAnalysisError::NoWordleAnswers),
+        ));
+    }
+}
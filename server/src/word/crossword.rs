@@ -0,0 +1,583 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use super::word_index::{MAX_WORD_LENGTH, MIN_WORD_LENGTH, WordIndexN};
+use super::word_set::WordSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Across,
+    Down,
+}
+
+/// A run of two or more squares between blocks or the edge of the grid,
+/// which the fill has to make a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrosswordSlot {
+    pub direction: Direction,
+    pub row: usize,
+    pub col: usize,
+    pub length: usize,
+}
+
+impl CrosswordSlot {
+    /// The (row, col) of each square, in reading order.
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> {
+        let slot = *self;
+        (0..slot.length).map(move |i| match slot.direction {
+            Direction::Across => (slot.row, slot.col + i),
+            Direction::Down => (slot.row + i, slot.col),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CrosswordError {
+    EmptyGrid,
+    RaggedGrid { row: usize, length: usize, expected: usize },
+    InvalidCell { row: usize, col: usize, cell: char },
+    /// There's no index for words of this slot's length.
+    UnsupportedSlotLength(CrosswordSlot),
+}
+
+impl fmt::Display for CrosswordError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CrosswordError::EmptyGrid => {
+                write!(formatter, "the grid has no squares")
+            },
+            CrosswordError::RaggedGrid { row, length, expected } => {
+                write!(formatter, "row {} has {} squares, but the first row has {}", row + 1, length, expected)
+            },
+            CrosswordError::InvalidCell { row, col, cell } => {
+                write!(
+                    formatter,
+                    "'{}' at row {}, column {} is not a letter, '#' for a block or '.', '_' or '?' for a blank",
+                    cell,
+                    row + 1,
+                    col + 1,
+                )
+            },
+            CrosswordError::UnsupportedSlotLength(slot) => {
+                write!(
+                    formatter,
+                    "the {:?} slot at row {}, column {} is {} letters, but words must be {} to {}",
+                    slot.direction,
+                    slot.row + 1,
+                    slot.col + 1,
+                    slot.length,
+                    MIN_WORD_LENGTH,
+                    MAX_WORD_LENGTH,
+                )
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Block,
+    Blank,
+    Letter(char),
+}
+
+/// A crossword grid, some of it filled in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrosswordGrid {
+    width: usize,
+    height: usize,
+    // row by row
+    cells: Vec<Cell>,
+}
+
+impl CrosswordGrid {
+    /// Reads rows separated by whitespace or `/`, with `#` for a block, `.`,
+    /// `_` or `?` for a blank square and letters for filled in ones, e.g.
+    /// `SL.. .##. .##. ....`. A single row is a pattern to fill, like `S.A.T`.
+    pub fn parse(grid: &str) -> Result<CrosswordGrid, CrosswordError> {
+        let rows: Vec<&str> = grid.split(|ch: char| ch.is_whitespace() || ch == '/')
+            .filter(|row| !row.is_empty())
+            .collect();
+        let width = rows.first().map(|row| row.chars().count()).ok_or(CrosswordError::EmptyGrid)?;
+
+        let mut cells: Vec<Cell> = vec![];
+        for (row, squares) in rows.iter().enumerate() {
+            let length = squares.chars().count();
+            if length != width {
+                return Err(CrosswordError::RaggedGrid {
+                    row,
+                    length,
+                    expected: width,
+                });
+            }
+
+            for (col, cell) in squares.chars().enumerate() {
+                cells.push(match cell {
+                    '#' => Cell::Block,
+                    '.' | '_' | '?' => Cell::Blank,
+                    _ if cell.is_ascii_alphabetic() => Cell::Letter(cell.to_ascii_uppercase()),
+                    _ => return Err(CrosswordError::InvalidCell { row, col, cell }),
+                });
+            }
+        }
+
+        Ok(CrosswordGrid {
+            width,
+            height: rows.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The across slots in reading order, then the down ones.
+    pub fn slots(&self) -> Vec<CrosswordSlot> {
+        let mut slots: Vec<CrosswordSlot> = vec![];
+
+        for direction in [Direction::Across, Direction::Down].iter() {
+            let (lines, line_length) = match direction {
+                Direction::Across => (self.height, self.width),
+                Direction::Down => (self.width, self.height),
+            };
+            let position = |line: usize, i: usize| match direction {
+                Direction::Across => (line, i),
+                Direction::Down => (i, line),
+            };
+
+            for line in 0..lines {
+                let mut start = 0;
+                while start < line_length {
+                    let mut end = start;
+                    while end < line_length && self.cell(position(line, end)) != Cell::Block {
+                        end += 1;
+                    }
+
+                    if end - start >= 2 {
+                        let (row, col) = position(line, start);
+                        slots.push(CrosswordSlot {
+                            direction: *direction,
+                            row,
+                            col,
+                            length: end - start,
+                        });
+                    }
+                    start = end + 1;
+                }
+            }
+        }
+
+        // reading order for the down slots too
+        slots.sort_by_key(|slot| (slot.direction == Direction::Down, slot.row, slot.col));
+        slots
+    }
+
+    fn cell(&self, (row, col): (usize, usize)) -> Cell {
+        self.cells[row * self.width + col]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillLimits {
+    /// Stop after this many fills.
+    pub max_solutions: usize,
+    /// Stop searching after this long, keeping the fills found so far.
+    pub time_limit: Option<Duration>,
+}
+
+impl Default for FillLimits {
+    fn default() -> FillLimits {
+        FillLimits {
+            max_solutions: 1,
+            time_limit: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrosswordFill {
+    /// The filled in grid, with `#` for blocks.
+    pub rows: Vec<String>,
+    /// The word in each slot, in the order of `CrosswordGrid::slots`.
+    pub words: Vec<(CrosswordSlot, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillResult {
+    pub fills: Vec<CrosswordFill>,
+    /// Whether the time limit ran out before the search found
+    /// `max_solutions` fills or ran out of words to try.
+    pub timed_out: bool,
+}
+
+/// Fills crossword grids so that every slot is a word from the list, no
+/// word twice.
+///
+/// The possible words for each slot are kept as a `WordSet` over the word
+/// index for its length. Crossing slots narrow each other down to the words
+/// that agree on the shared square, until nothing changes, and then the
+/// slot with the fewest words left is tried one word at a time.
+#[derive(Debug)]
+pub struct CrosswordSolver {
+    // one per length from MIN_WORD_LENGTH
+    indexes: Vec<WordIndexN>,
+}
+
+impl CrosswordSolver {
+    /// Words that aren't `MIN_WORD_LENGTH` to `MAX_WORD_LENGTH` letters from
+    /// A to Z are left out.
+    pub fn new(words: &[String]) -> CrosswordSolver {
+        let indexes = (MIN_WORD_LENGTH..=MAX_WORD_LENGTH)
+            .map(|length| {
+                let words_of_length = words.iter()
+                    .filter(|word| word.len() == length && word.bytes().all(|b| b.is_ascii_alphabetic()))
+                    .map(|word| word.to_ascii_uppercase())
+                    .collect();
+                WordIndexN::new(length, words_of_length).expect("words were checked above")
+            })
+            .collect();
+
+        CrosswordSolver {
+            indexes,
+        }
+    }
+
+    pub fn solve(&self, grid: &str, limits: &FillLimits) -> Result<FillResult, CrosswordError> {
+        self.fill(&CrosswordGrid::parse(grid)?, limits)
+    }
+
+    /// Fills in the blank squares of `grid`, finding up to
+    /// `limits.max_solutions` fills.
+    pub fn fill(&self, grid: &CrosswordGrid, limits: &FillLimits) -> Result<FillResult, CrosswordError> {
+        let slots = grid.slots();
+        if let Some(slot) = slots.iter().find(|slot| !(MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&slot.length)) {
+            return Err(CrosswordError::UnsupportedSlotLength(*slot));
+        }
+
+        let mut search = FillSearch {
+            solver: self,
+            grid,
+            crossings: crossings(grid, &slots),
+            slots,
+            max_solutions: limits.max_solutions,
+            deadline: limits.time_limit.map(|limit| Instant::now() + limit),
+            fills: vec![],
+            timed_out: false,
+        };
+
+        let mut domains: Vec<WordSet> = search.slots.iter()
+            .map(|slot| {
+                let index = self.index_for(slot);
+                let mut domain = WordSet::full(index.word_count());
+                for (position, cell) in slot.cells().enumerate() {
+                    if let Cell::Letter(letter) = grid.cell(cell) {
+                        match index.letter_location_set(letter, position + 1) {
+                            Some(words) => domain.intersect_with(words),
+                            None => domain = WordSet::empty(index.word_count()),
+                        }
+                    }
+                }
+                domain
+            })
+            .collect();
+
+        let all_slots: Vec<usize> = (0..search.slots.len()).collect();
+        if search.propagate(&mut domains, &all_slots) {
+            let mut assigned = vec![false; search.slots.len()];
+            search.search(domains, &mut assigned);
+        }
+
+        Ok(FillResult {
+            fills: search.fills,
+            timed_out: search.timed_out,
+        })
+    }
+
+    fn index_for(&self, slot: &CrosswordSlot) -> &WordIndexN {
+        &self.indexes[slot.length - MIN_WORD_LENGTH]
+    }
+}
+
+/// Where one slot crosses another.
+#[derive(Debug)]
+struct Crossing {
+    other: usize,
+    // the shared square's position in each slot, from 0
+    position: usize,
+    other_position: usize,
+}
+
+fn crossings(grid: &CrosswordGrid, slots: &[CrosswordSlot]) -> Vec<Vec<Crossing>> {
+    let mut slot_at: Vec<Vec<(usize, usize)>> = vec![vec![]; grid.width * grid.height];
+    for (index, slot) in slots.iter().enumerate() {
+        for (position, (row, col)) in slot.cells().enumerate() {
+            slot_at[row * grid.width + col].push((index, position));
+        }
+    }
+
+    let mut crossings: Vec<Vec<Crossing>> = slots.iter().map(|_| vec![]).collect();
+    for in_cell in slot_at.iter().filter(|in_cell| in_cell.len() == 2) {
+        let (a, a_position) = in_cell[0];
+        let (b, b_position) = in_cell[1];
+        crossings[a].push(Crossing { other: b, position: a_position, other_position: b_position });
+        crossings[b].push(Crossing { other: a, position: b_position, other_position: a_position });
+    }
+
+    crossings
+}
+
+struct FillSearch<'a> {
+    solver: &'a CrosswordSolver,
+    grid: &'a CrosswordGrid,
+    slots: Vec<CrosswordSlot>,
+    crossings: Vec<Vec<Crossing>>,
+    max_solutions: usize,
+    deadline: Option<Instant>,
+    fills: Vec<CrosswordFill>,
+    timed_out: bool,
+}
+
+impl<'a> FillSearch<'a> {
+    fn should_stop(&mut self) -> bool {
+        if !self.timed_out && self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.timed_out = true;
+        }
+
+        self.timed_out || self.fills.len() >= self.max_solutions
+    }
+
+    fn search(&mut self, domains: Vec<WordSet>, assigned: &mut Vec<bool>) {
+        if self.should_stop() {
+            return;
+        }
+
+        let next_slot = (0..self.slots.len())
+            .filter(|slot| !assigned[*slot])
+            .min_by_key(|slot| domains[*slot].len());
+        let slot = match next_slot {
+            Some(slot) => slot,
+            None => {
+                self.record_fill(&domains);
+                return;
+            },
+        };
+
+        let words: Vec<usize> = domains[slot].ids().collect();
+        for word in words {
+            if self.should_stop() {
+                return;
+            }
+
+            let mut next = domains.clone();
+            next[slot] = WordSet::empty(next[slot].size());
+            next[slot].insert(word);
+
+            // no word twice, so it's gone from the other slots of its length
+            let mut changed = vec![slot];
+            for other in (0..self.slots.len()).filter(|other| *other != slot) {
+                if self.slots[other].length == self.slots[slot].length && next[other].contains(word) {
+                    next[other].remove(word);
+                    changed.push(other);
+                }
+            }
+
+            if self.propagate(&mut next, &changed) {
+                assigned[slot] = true;
+                self.search(next, assigned);
+                assigned[slot] = false;
+            }
+        }
+    }
+
+    /// Narrows down the slots crossing the `changed` ones, and the ones
+    /// crossing those, until nothing changes. False if a slot has no words
+    /// left.
+    fn propagate(&self, domains: &mut [WordSet], changed: &[usize]) -> bool {
+        if changed.iter().any(|slot| domains[*slot].is_empty()) {
+            return false;
+        }
+
+        let mut queue: VecDeque<usize> = changed.iter().copied().collect();
+        while let Some(slot) = queue.pop_front() {
+            for crossing in self.crossings[slot].iter() {
+                if self.revise(domains, slot, crossing) {
+                    if domains[crossing.other].is_empty() {
+                        return false;
+                    }
+                    if !queue.contains(&crossing.other) {
+                        queue.push_back(crossing.other);
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    // Keeps the words of the crossing slot whose letter in the shared square
+    // some word of `slot` has too. True if any were dropped.
+    fn revise(&self, domains: &mut [WordSet], slot: usize, crossing: &Crossing) -> bool {
+        let index = self.solver.index_for(&self.slots[slot]);
+        let other_index = self.solver.index_for(&self.slots[crossing.other]);
+
+        let mut supported = WordSet::empty(other_index.word_count());
+        for letter in 'A'..='Z' {
+            let fits_slot = index.letter_location_set(letter, crossing.position + 1)
+                .is_some_and(|words| domains[slot].intersects(words));
+            if !fits_slot {
+                continue;
+            }
+
+            if let Some(words) = other_index.letter_location_set(letter, crossing.other_position + 1) {
+                supported.union_with(words);
+            }
+        }
+
+        let before = domains[crossing.other].len();
+        domains[crossing.other].intersect_with(&supported);
+        domains[crossing.other].len() != before
+    }
+
+    fn record_fill(&mut self, domains: &[WordSet]) {
+        let mut squares: Vec<char> = self.grid.cells.iter()
+            .map(|cell| match cell {
+                Cell::Block => '#',
+                Cell::Blank => '.',
+                Cell::Letter(letter) => *letter,
+            })
+            .collect();
+
+        let mut words: Vec<(CrosswordSlot, String)> = vec![];
+        for (slot, domain) in self.slots.iter().zip(domains.iter()) {
+            let id = domain.ids().next().expect("every slot has a word");
+            let word = self.solver.index_for(slot).word(id);
+            for ((row, col), letter) in slot.cells().zip(word.chars()) {
+                squares[row * self.grid.width + col] = letter;
+            }
+            words.push((*slot, word.to_owned()));
+        }
+
+        self.fills.push(CrosswordFill {
+            rows: squares.chunks(self.grid.width)
+                .map(|row| row.iter().collect())
+                .collect(),
+            words,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_solver(words: &[&str]) -> CrosswordSolver {
+        let words: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        CrosswordSolver::new(&words)
+    }
+
+    fn rows_of(result: &FillResult) -> Vec<Vec<String>> {
+        result.fills.iter().map(|fill| fill.rows.clone()).collect()
+    }
+
+    const RING: &str = "..../.##./.##./....";
+    const RING_WORDS: [&str; 8] = ["SLAB", "SEAT", "BOLT", "TENT", "SLAM", "STAR", "TOTS", "ZZZZ"];
+
+    #[test]
+    fn test_slots() {
+        let grid = CrosswordGrid::parse("S.A.T\n.#.#.\n.....").unwrap();
+        assert_eq!((grid.width(), grid.height()), (5, 3));
+
+        let slots = grid.slots();
+        let across = |row, col, length| CrosswordSlot { direction: Direction::Across, row, col, length };
+        let down = |row, col, length| CrosswordSlot { direction: Direction::Down, row, col, length };
+        assert_eq!(slots, vec![across(0, 0, 5), across(2, 0, 5), down(0, 0, 3), down(0, 2, 3), down(0, 4, 3)]);
+        assert_eq!(slots[3].cells().collect::<Vec<(usize, usize)>>(), vec![(0, 2), (1, 2), (2, 2)]);
+
+        // single squares between blocks aren't slots
+        assert_eq!(CrosswordGrid::parse("..#.#....").unwrap().slots(), vec![across(0, 0, 2), across(0, 5, 4)]);
+    }
+
+    #[test]
+    fn test_fill_ring() {
+        let solver = create_test_solver(&RING_WORDS);
+        let limits = FillLimits { max_solutions: 10, time_limit: None };
+
+        // a fill and its mirror image
+        let result = solver.solve(RING, &limits).unwrap();
+        assert!(!result.timed_out);
+        assert_eq!(rows_of(&result), vec![
+            vec!["SLAB", "E##O", "A##L", "TENT"],
+            vec!["SEAT", "L##E", "A##N", "BOLT"],
+        ]);
+        assert_eq!(result.fills[0].words[0].1, "SLAB");
+        assert_eq!(result.fills[0].words[3].1, "BOLT");
+
+        let result = solver.solve(RING, &FillLimits::default()).unwrap();
+        assert_eq!(result.fills.len(), 1);
+
+        // fixed letters pick one
+        let result = solver.solve("..../.##o/.##./....", &limits).unwrap();
+        assert_eq!(rows_of(&result), vec![vec!["SLAB", "E##O", "A##L", "TENT"]]);
+    }
+
+    #[test]
+    fn test_no_word_twice() {
+        let limits = FillLimits { max_solutions: 10, time_limit: None };
+
+        let result = create_test_solver(&["SLAB"]).solve("..../####/....", &limits).unwrap();
+        assert!(result.fills.is_empty());
+        assert!(!result.timed_out);
+
+        let result = create_test_solver(&["SLAB", "BOLT"]).solve("..../####/....", &limits).unwrap();
+        assert_eq!(rows_of(&result), vec![
+            vec!["SLAB", "####", "BOLT"],
+            vec!["BOLT", "####", "SLAB"],
+        ]);
+    }
+
+    #[test]
+    fn test_pattern() {
+        let solver = create_test_solver(&["SHALT", "SMART", "START", "SLANT", "shaft", "SPATE"]);
+        let limits = FillLimits { max_solutions: 10, time_limit: None };
+
+        let result = solver.solve("s.a.t", &limits).unwrap();
+        let words: Vec<&str> = result.fills.iter().map(|fill| fill.rows[0].as_str()).collect();
+        assert_eq!(words, vec!["SHALT", "SMART", "START", "SLANT", "SHAFT"]);
+    }
+
+    #[test]
+    fn test_time_limit() {
+        let solver = create_test_solver(&RING_WORDS);
+        let limits = FillLimits { max_solutions: 10, time_limit: Some(Duration::from_secs(0)) };
+
+        let result = solver.solve(RING, &limits).unwrap();
+        assert!(result.timed_out);
+        assert!(result.fills.is_empty());
+    }
+
+    #[test]
+    fn test_errors() {
+        let solver = create_test_solver(&RING_WORDS);
+        let limits = FillLimits::default();
+
+        assert_eq!(solver.solve(" / ", &limits), Err(CrosswordError::EmptyGrid));
+        assert_eq!(
+            solver.solve("..../...", &limits),
+            Err(CrosswordError::RaggedGrid { row: 1, length: 3, expected: 4 }),
+        );
+        assert_eq!(
+            solver.solve("..1.", &limits),
+            Err(CrosswordError::InvalidCell { row: 0, col: 2, cell: '1' }),
+        );
+        assert_eq!(
+            solver.solve("....#...", &limits).unwrap_err().to_string(),
+            "the Across slot at row 1, column 6 is 3 letters, but words must be 4 to 11",
+        );
+    }
+}
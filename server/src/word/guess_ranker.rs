@@ -0,0 +1,270 @@
+use std::collections::{HashMap, HashSet};
+
+use super::wordle::{Feedback, LetterFeedback, WordleError, normalize_word, score_guess};
+
+/// The number of different feedback patterns for a five letter guess.
+pub const PATTERN_COUNT: usize = 243;
+/// The pattern code of a guess that was the answer.
+pub const ALL_GREEN: u8 = (PATTERN_COUNT - 1) as u8;
+
+/// How to pick between guesses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankingStrategy {
+    /// The most information about the answer on average.
+    Entropy,
+    /// The fewest candidates left in the worst case.
+    Minimax,
+}
+
+impl RankingStrategy {
+    pub fn from_name(name: &str) -> Option<RankingStrategy> {
+        match name {
+            "entropy" => Some(RankingStrategy::Entropy),
+            "minimax" => Some(RankingStrategy::Minimax),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuessSuggestion {
+    pub guess: String,
+    /// The expected information from the feedback, in bits.
+    pub entropy: f64,
+    /// How many candidates are left on average, not counting the one left
+    /// when the guess is the answer.
+    pub expected_remaining: f64,
+    /// How many candidates are left after the least helpful feedback.
+    pub worst_case_remaining: usize,
+    /// Whether the guess could itself be the answer.
+    pub is_candidate: bool,
+}
+
+/// Packs feedback into a number below `PATTERN_COUNT`, one base 3 digit per
+/// letter.
+pub fn pattern_code(feedback: &Feedback) -> u8 {
+    feedback.iter().fold(0, |code, letter| {
+        code * 3 + match letter {
+            LetterFeedback::Gray => 0,
+            LetterFeedback::Yellow => 1,
+            LetterFeedback::Green => 2,
+        }
+    })
+}
+
+/// Ranks next guesses for a Wordle game. The feedback every allowed guess
+/// would get against every possible answer is worked out up front, so
+/// ranking only has to count patterns.
+#[derive(Debug)]
+pub struct GuessRanker {
+    answers: Vec<String>,
+    answer_indexes: HashMap<String, usize>,
+    // the answers first, then the other allowed guesses
+    guesses: Vec<String>,
+    // guesses.len() rows of answers.len() pattern codes
+    patterns: Vec<u8>,
+}
+
+impl GuessRanker {
+    /// `extra_guesses` are words that are allowed as guesses but can't be
+    /// the answer. They're often the best way to split up the candidates.
+    pub fn new(answers: &[String], extra_guesses: &[String]) -> Result<GuessRanker, WordleError> {
+        let mut guesses: Vec<String> = vec![];
+        let mut answer_indexes: HashMap<String, usize> = HashMap::new();
+        for word in answers {
+            let word = normalize_word(word)?;
+            if !answer_indexes.contains_key(&word) {
+                answer_indexes.insert(word.clone(), guesses.len());
+                guesses.push(word);
+            }
+        }
+        let answers = guesses.clone();
+
+        let mut seen_extra_guesses: HashSet<String> = HashSet::new();
+        for word in extra_guesses {
+            let word = normalize_word(word)?;
+            if !answer_indexes.contains_key(&word) && seen_extra_guesses.insert(word.clone()) {
+                guesses.push(word);
+            }
+        }
+
+        let patterns = guesses.iter()
+            .flat_map(|guess| {
+                answers.iter().map(move |answer| pattern_code(&score_guess(guess, answer)))
+            })
+            .collect();
+
+        Ok(GuessRanker {
+            answers,
+            answer_indexes,
+            guesses,
+            patterns,
+        })
+    }
+
+    pub fn answers(&self) -> &[String] {
+        &self.answers
+    }
+
+    /// The `count` best guesses when the answer is one of `candidates`,
+    /// best first. Ties go to guesses that could be the answer, then
+    /// alphabetically.
+    pub fn suggest(
+        &self,
+        candidates: &[String],
+        strategy: RankingStrategy,
+        count: usize,
+    ) -> Result<Vec<GuessSuggestion>, WordleError> {
+        if candidates.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut suggestions = self.evaluate_all(candidates)?;
+        suggestions.sort_by(|a, b| {
+            let by_strategy = match strategy {
+                RankingStrategy::Entropy => b.entropy.total_cmp(&a.entropy),
+                RankingStrategy::Minimax => a.worst_case_remaining.cmp(&b.worst_case_remaining)
+                    .then_with(|| a.expected_remaining.total_cmp(&b.expected_remaining)),
+            };
+            by_strategy
+                .then_with(|| b.is_candidate.cmp(&a.is_candidate))
+                .then_with(|| a.guess.cmp(&b.guess))
+        });
+        suggestions.truncate(count);
+
+        Ok(suggestions)
+    }
+
+    /// How every allowed guess does against `candidates`, in the order the
+    /// answers and then the extra guesses were given.
+    pub fn evaluate_all(&self, candidates: &[String]) -> Result<Vec<GuessSuggestion>, WordleError> {
+        let candidate_indexes = candidates.iter()
+            .map(|word| {
+                self.answer_indexes.get(word)
+                    .copied()
+                    .ok_or_else(|| WordleError::UnknownAnswer(word.to_owned()))
+            })
+            .collect::<Result<Vec<usize>, WordleError>>()?;
+
+        let mut is_candidate = vec![false; self.guesses.len()];
+        for index in candidate_indexes.iter() {
+            is_candidate[*index] = true;
+        }
+
+        Ok((0..self.guesses.len())
+            .map(|guess| self.evaluate(guess, &candidate_indexes, is_candidate[guess]))
+            .collect())
+    }
+
+    fn evaluate(&self, guess: usize, candidates: &[usize], is_candidate: bool) -> GuessSuggestion {
+        let row = &self.patterns[guess * self.answers.len()..(guess + 1) * self.answers.len()];
+        let mut bucket_sizes = [0usize; PATTERN_COUNT];
+        for candidate in candidates {
+            bucket_sizes[row[*candidate] as usize] += 1;
+        }
+
+        let total = candidates.len() as f64;
+        let mut entropy = 0.0;
+        let mut expected_remaining = 0.0;
+        let mut worst_case_remaining = 0;
+        for (pattern, size) in bucket_sizes.iter().enumerate().filter(|(_, size)| **size > 0) {
+            let probability = *size as f64 / total;
+            entropy -= probability * probability.log2();
+            if pattern != ALL_GREEN as usize {
+                expected_remaining += probability * *size as f64;
+                worst_case_remaining = worst_case_remaining.max(*size);
+            }
+        }
+
+        GuessSuggestion {
+            guess: self.guesses[guess].clone(),
+            entropy,
+            expected_remaining,
+            worst_case_remaining,
+            is_candidate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn create_test_ranker() -> GuessRanker {
+        GuessRanker::new(
+            &to_strings(&["BATCH", "CATCH", "HATCH", "LATCH", "MATCH", "PATCH", "WATCH", "CRANE"]),
+            &to_strings(&["blimp", "fjord", "BATCH"]),
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_pattern_code() {
+        use LetterFeedback::{Gray, Green, Yellow};
+
+        assert_eq!(pattern_code(&[Gray; 5]), 0);
+        assert_eq!(pattern_code(&[Green; 5]), ALL_GREEN);
+        assert_eq!(pattern_code(&[Gray, Gray, Gray, Yellow, Green]), 5);
+        assert_eq!(pattern_code(&[Yellow, Gray, Gray, Gray, Gray]), 81);
+    }
+
+    #[test]
+    fn test_outside_guess_splits_candidates() {
+        let ranker = create_test_ranker();
+        let candidates = to_strings(&["BATCH", "CATCH", "HATCH", "LATCH", "MATCH", "PATCH", "WATCH"]);
+
+        let suggestions = ranker.suggest(&candidates, RankingStrategy::Entropy, 3).unwrap();
+
+        // BLIMP tells BATCH, LATCH, MATCH and PATCH apart, and leaves the rest
+        let best = &suggestions[0];
+        assert_eq!(best.guess, "BLIMP");
+        assert!(!best.is_candidate);
+        assert_eq!(best.worst_case_remaining, 3);
+        assert!((best.expected_remaining - 13.0 / 7.0).abs() < 1e-9);
+        let expected_entropy = 4.0 / 7.0 * 7f64.log2() + 3.0 / 7.0 * (7.0 / 3.0f64).log2();
+        assert!((best.entropy - expected_entropy).abs() < 1e-9);
+
+        // any _ATCH word only checks its own first letter, and those tie
+        // with each other
+        assert_eq!(suggestions[1].guess, "BATCH");
+        assert!(suggestions[1].is_candidate);
+        assert_eq!(suggestions[1].worst_case_remaining, 6);
+        assert!((suggestions[1].expected_remaining - 36.0 / 7.0).abs() < 1e-9);
+        assert_eq!(suggestions.len(), 3);
+    }
+
+    #[test]
+    fn test_minimax_and_ties() {
+        let ranker = create_test_ranker();
+
+        let candidates = to_strings(&["CATCH", "HATCH", "WATCH"]);
+        let suggestions = ranker.suggest(&candidates, RankingStrategy::Minimax, 10).unwrap();
+        // any of the three leaves at most two, and can win on the spot
+        assert_eq!(suggestions[0].guess, "CATCH");
+        assert_eq!(suggestions[0].worst_case_remaining, 2);
+
+        // one candidate left: guess it
+        let last = ranker.suggest(&to_strings(&["WATCH"]), RankingStrategy::Minimax, 1).unwrap();
+        assert_eq!(last[0].guess, "WATCH");
+        assert_eq!(last[0].expected_remaining, 0.0);
+        assert_eq!(last[0].entropy, 0.0);
+    }
+
+    #[test]
+    fn test_unknown_candidates() {
+        let ranker = create_test_ranker();
+
+        assert_eq!(ranker.answers().len(), 8);
+        // extra guesses can't be the answer
+        assert_eq!(
+            ranker.suggest(&to_strings(&["BLIMP"]), RankingStrategy::Entropy, 1),
+            Err(WordleError::UnknownAnswer("BLIMP".to_owned())),
+        );
+        assert_eq!(ranker.suggest(&[], RankingStrategy::Entropy, 1), Ok(vec![]));
+        assert_eq!(RankingStrategy::from_name("minimax"), Some(RankingStrategy::Minimax));
+        assert_eq!(RankingStrategy::from_name("greedy"), None);
+    }
+}
@@ -0,0 +1,548 @@
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::games::boggle_like::read_word_list;
+
+pub const SPELLING_BEE_LETTERS: usize = 7;
+pub const SPELLING_BEE_MIN_WORD_LENGTH: usize = 4;
+pub const SPELLING_BEE_PANGRAM_BONUS: u32 = 7;
+pub const LETTER_BOXED_MIN_WORD_LENGTH: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LetterPuzzleError {
+    InvalidLetter(char),
+    RepeatedLetter(char),
+    WrongLetterCount { expected: usize, found: usize },
+    CenterNotInLetters(char),
+    TooFewSides(usize),
+}
+
+impl fmt::Display for LetterPuzzleError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LetterPuzzleError::InvalidLetter(letter) => {
+                write!(formatter, "'{}' is not a letter from A to Z", letter)
+            },
+            LetterPuzzleError::RepeatedLetter(letter) => {
+                write!(formatter, "'{}' is in the puzzle more than once", letter)
+            },
+            LetterPuzzleError::WrongLetterCount { expected, found } => {
+                write!(formatter, "expected {} letters, got {}", expected, found)
+            },
+            LetterPuzzleError::CenterNotInLetters(letter) => {
+                write!(formatter, "the center letter '{}' is not one of the puzzle's letters", letter)
+            },
+            LetterPuzzleError::TooFewSides(sides) => {
+                write!(formatter, "a puzzle needs at least 2 sides, not {}", sides)
+            },
+        }
+    }
+}
+
+/// A set of the letters A to Z, one bit each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LetterSet(u32);
+
+impl LetterSet {
+    /// Reads distinct letters in either case.
+    pub fn parse(letters: &str) -> Result<LetterSet, LetterPuzzleError> {
+        let mut set = LetterSet::default();
+        for letter in letters.chars() {
+            if !letter.is_ascii_alphabetic() {
+                return Err(LetterPuzzleError::InvalidLetter(letter));
+            }
+            let letter = letter.to_ascii_uppercase();
+            if set.contains(letter as u8) {
+                return Err(LetterPuzzleError::RepeatedLetter(letter));
+            }
+            set.0 |= letter_bit(letter as u8);
+        }
+
+        Ok(set)
+    }
+
+    /// The letters of an upper case word, or `None` if it has anything
+    /// other than A to Z.
+    pub fn of_word(word: &str) -> Option<LetterSet> {
+        word.bytes()
+            .try_fold(0, |set, letter| {
+                if letter.is_ascii_uppercase() {
+                    Some(set | letter_bit(letter))
+                } else {
+                    None
+                }
+            })
+            .map(LetterSet)
+    }
+
+    pub fn contains(&self, letter: u8) -> bool {
+        letter.is_ascii_uppercase() && self.0 & letter_bit(letter) != 0
+    }
+
+    pub fn is_subset_of(&self, other: &LetterSet) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    pub fn union(&self, other: &LetterSet) -> LetterSet {
+        LetterSet(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+fn letter_bit(letter: u8) -> u32 {
+    1 << (letter - b'A')
+}
+
+/// What makes a word valid in a "use only these letters" puzzle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LetterSetRules {
+    pub allowed: LetterSet,
+    /// Letters every word has to use.
+    pub required: LetterSet,
+    pub min_word_length: usize,
+    /// Groups of letters, like the sides of a Letter Boxed square, that a
+    /// word can't use two letters from in a row. Letters can be used again
+    /// unless they're in a group.
+    pub sides: Vec<LetterSet>,
+}
+
+impl LetterSetRules {
+    pub fn allows(&self, word: &str) -> bool {
+        match LetterSet::of_word(word) {
+            Some(letters) => {
+                word.len() >= self.min_word_length
+                    && letters.is_subset_of(&self.allowed)
+                    && self.required.is_subset_of(&letters)
+                    && word.as_bytes().windows(2).all(|pair| !self.same_side(pair[0], pair[1]))
+            },
+            None => false,
+        }
+    }
+
+    fn same_side(&self, a: u8, b: u8) -> bool {
+        self.sides.iter().any(|side| side.contains(a) && side.contains(b))
+    }
+}
+
+/// A Spelling Bee puzzle: words of four or more letters made from seven,
+/// using the center one. Letters can be used more than once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpellingBee {
+    letters: LetterSet,
+    center: char,
+}
+
+impl SpellingBee {
+    /// `letters` are all seven letters, `center` included.
+    pub fn new(letters: &str, center: char) -> Result<SpellingBee, LetterPuzzleError> {
+        let letter_set = LetterSet::parse(letters)?;
+        if letter_set.len() != SPELLING_BEE_LETTERS {
+            return Err(LetterPuzzleError::WrongLetterCount {
+                expected: SPELLING_BEE_LETTERS,
+                found: letter_set.len(),
+            });
+        }
+        let center = center.to_ascii_uppercase();
+        if !center.is_ascii_uppercase() || !letter_set.contains(center as u8) {
+            return Err(LetterPuzzleError::CenterNotInLetters(center));
+        }
+
+        Ok(SpellingBee {
+            letters: letter_set,
+            center,
+        })
+    }
+
+    pub fn rules(&self) -> LetterSetRules {
+        LetterSetRules {
+            allowed: self.letters,
+            required: LetterSet::parse(&self.center.to_string()).expect("the center was checked"),
+            min_word_length: SPELLING_BEE_MIN_WORD_LENGTH,
+            sides: vec![],
+        }
+    }
+
+    /// A word that uses all seven letters.
+    pub fn is_pangram(&self, word: &str) -> bool {
+        LetterSet::of_word(word) == Some(self.letters)
+    }
+
+    /// One point for a four letter word and one a letter for longer ones,
+    /// with a bonus for pangrams. `word` is assumed to be valid.
+    pub fn score(&self, word: &str) -> u32 {
+        let length_score = match word.len() {
+            SPELLING_BEE_MIN_WORD_LENGTH => 1,
+            length => length as u32,
+        };
+
+        if self.is_pangram(word) {
+            length_score + SPELLING_BEE_PANGRAM_BONUS
+        } else {
+            length_score
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpellingBeeWord {
+    pub word: String,
+    pub score: u32,
+    pub is_pangram: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpellingBeeSolution {
+    /// Highest scoring first, and alphabetically within a score.
+    pub words: Vec<SpellingBeeWord>,
+    pub total_score: u32,
+}
+
+impl SpellingBeeSolution {
+    pub fn pangrams(&self) -> Vec<&str> {
+        self.words.iter()
+            .filter(|word| word.is_pangram)
+            .map(|word| word.word.as_str())
+            .collect()
+    }
+}
+
+/// A Letter Boxed puzzle: letters around the sides of a square, to be
+/// linked into a chain of words that uses every letter. Each word starts
+/// with the last letter of the one before, and no two letters in a row of a
+/// word come from the same side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LetterBoxed {
+    sides: Vec<LetterSet>,
+    letters: LetterSet,
+}
+
+impl LetterBoxed {
+    /// Reads sides separated by whitespace or `/`, e.g. `FOA LCV KHN SIG`.
+    /// No letter can be on two sides.
+    pub fn parse(sides: &str) -> Result<LetterBoxed, LetterPuzzleError> {
+        let sides: Vec<&str> = sides.split(|ch: char| ch.is_whitespace() || ch == '/')
+            .filter(|side| !side.is_empty())
+            .collect();
+        if sides.len() < 2 {
+            return Err(LetterPuzzleError::TooFewSides(sides.len()));
+        }
+
+        LetterSet::parse(&sides.concat())?;
+        let sides = sides.iter()
+            .map(|side| LetterSet::parse(side))
+            .collect::<Result<Vec<LetterSet>, LetterPuzzleError>>()?;
+        let letters = sides.iter().fold(LetterSet::default(), |letters, side| letters.union(side));
+
+        Ok(LetterBoxed {
+            sides,
+            letters,
+        })
+    }
+
+    pub fn rules(&self) -> LetterSetRules {
+        LetterSetRules {
+            allowed: self.letters,
+            required: LetterSet::default(),
+            min_word_length: LETTER_BOXED_MIN_WORD_LENGTH,
+            sides: self.sides.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainLimits {
+    /// The most words in a chain.
+    pub max_words: usize,
+    /// Stop after this many chains.
+    pub max_chains: usize,
+    /// Stop searching after this long, keeping the chains found so far.
+    pub time_limit: Option<Duration>,
+}
+
+impl Default for ChainLimits {
+    fn default() -> ChainLimits {
+        ChainLimits {
+            max_words: 2,
+            max_chains: 10,
+            time_limit: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LetterBoxedSolution {
+    /// Every word the puzzle allows, in word list order.
+    pub words: Vec<String>,
+    /// Chains of words that use every letter, the fewest words first.
+    pub chains: Vec<Vec<String>>,
+    /// Whether the time limit ran out before the search found
+    /// `max_chains` chains or ran out of chains to try.
+    pub timed_out: bool,
+}
+
+/// Solves letter set puzzles against one word list, with each word's
+/// letters worked out up front.
+#[derive(Debug)]
+pub struct LetterPuzzleSolver {
+    words: Vec<(String, LetterSet)>,
+}
+
+impl LetterPuzzleSolver {
+    /// Words are upper cased, and ones with anything but the letters A to Z
+    /// are left out.
+    pub fn new(words: &[String]) -> LetterPuzzleSolver {
+        LetterPuzzleSolver {
+            words: words.iter()
+                .map(|word| word.to_ascii_uppercase())
+                .filter_map(|word| LetterSet::of_word(&word).map(|letters| (word, letters)))
+                .collect(),
+        }
+    }
+
+    pub fn from_file<P>(word_list_file_path: P) -> io::Result<LetterPuzzleSolver>
+        where P: AsRef<Path>,
+    {
+        Ok(LetterPuzzleSolver::new(&read_word_list(word_list_file_path)?))
+    }
+
+    /// The words `rules` allow, in word list order.
+    pub fn words_matching(&self, rules: &LetterSetRules) -> Vec<String> {
+        self.words.iter()
+            .filter(|(word, letters)| {
+                // the letter sets rule most words out before looking at the
+                // word itself
+                letters.is_subset_of(&rules.allowed)
+                    && rules.required.is_subset_of(letters)
+                    && rules.allows(word)
+            })
+            .map(|(word, _)| word.clone())
+            .collect()
+    }
+
+    pub fn spelling_bee(&self, puzzle: &SpellingBee) -> SpellingBeeSolution {
+        let mut words: Vec<SpellingBeeWord> = self.words_matching(&puzzle.rules())
+            .into_iter()
+            .map(|word| SpellingBeeWord {
+                score: puzzle.score(&word),
+                is_pangram: puzzle.is_pangram(&word),
+                word,
+            })
+            .collect();
+        words.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.word.cmp(&b.word)));
+        words.dedup_by(|a, b| a.word == b.word);
+
+        SpellingBeeSolution {
+            total_score: words.iter().map(|word| word.score).sum(),
+            words,
+        }
+    }
+
+    /// Finds up to `max_chains` chains of at most `max_words` words. All the
+    /// shortest chains are found before any longer ones.
+    pub fn letter_boxed(&self, puzzle: &LetterBoxed, limits: &ChainLimits) -> LetterBoxedSolution {
+        let words = self.words_matching(&puzzle.rules());
+        let chain_words: Vec<(&str, LetterSet)> = words.iter()
+            .map(|word| (word.as_str(), LetterSet::of_word(word).expect("words were checked")))
+            .collect();
+        let mut words_by_first_letter: Vec<Vec<usize>> = vec![vec![]; 26];
+        for (index, (word, _)) in chain_words.iter().enumerate() {
+            words_by_first_letter[(word.as_bytes()[0] - b'A') as usize].push(index);
+        }
+
+        let every_word: Vec<usize> = (0..chain_words.len()).collect();
+
+        let mut search = ChainSearch {
+            words: &chain_words,
+            every_word: &every_word,
+            words_by_first_letter: &words_by_first_letter,
+            all_letters: puzzle.letters,
+            max_chains: limits.max_chains,
+            deadline: limits.time_limit.map(|limit| Instant::now() + limit),
+            chain: vec![],
+            chains: vec![],
+            timed_out: false,
+        };
+        for length in 1..=limits.max_words {
+            if search.should_stop() {
+                break;
+            }
+            search.extend(length, LetterSet::default());
+        }
+        let chains = search.chains;
+        let timed_out = search.timed_out;
+
+        LetterBoxedSolution {
+            words,
+            chains,
+            timed_out,
+        }
+    }
+}
+
+struct ChainSearch<'a> {
+    words: &'a [(&'a str, LetterSet)],
+    // indexes into words, all of them and by the word's first letter
+    every_word: &'a [usize],
+    words_by_first_letter: &'a [Vec<usize>],
+    all_letters: LetterSet,
+    max_chains: usize,
+    deadline: Option<Instant>,
+    // indexes into words
+    chain: Vec<usize>,
+    chains: Vec<Vec<String>>,
+    timed_out: bool,
+}
+
+impl<'a> ChainSearch<'a> {
+    fn should_stop(&mut self) -> bool {
+        if !self.timed_out && self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.timed_out = true;
+        }
+
+        self.timed_out || self.chains.len() >= self.max_chains
+    }
+
+    // Adds words to the chain until it's `length` long, keeping the ones
+    // that use every letter.
+    fn extend(&mut self, length: usize, used: LetterSet) {
+        if self.chain.len() == length {
+            if used == self.all_letters {
+                self.chains.push(self.chain.iter().map(|index| self.words[*index].0.to_owned()).collect());
+            }
+            return;
+        }
+        if self.should_stop() {
+            return;
+        }
+
+        let is_last_word = self.chain.len() + 1 == length;
+        let next_words = match self.chain.last() {
+            Some(index) => {
+                let last_letter = *self.words[*index].0.as_bytes().last().unwrap();
+                &self.words_by_first_letter[(last_letter - b'A') as usize]
+            },
+            None => self.every_word,
+        };
+
+        for &index in next_words {
+            let letters = self.words[index].1;
+            if is_last_word && used.union(&letters) != self.all_letters {
+                continue;
+            }
+
+            self.chain.push(index);
+            self.extend(length, used.union(&letters));
+            self.chain.pop();
+            if self.should_stop() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_solver(words: &[&str]) -> LetterPuzzleSolver {
+        let words: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        LetterPuzzleSolver::new(&words)
+    }
+
+    #[test]
+    fn test_spelling_bee() {
+        let solver = create_test_solver(&[
+            "planted", "plant", "deal", "lead", "tape", "pedal", "plated", "tend", "ana",
+            "apple", "blend", "panted", "adept", "Lead", "can't",
+        ]);
+        let puzzle = SpellingBee::new("PLANTED", 'a').unwrap();
+
+        let solution = solver.spelling_bee(&puzzle);
+        let scored: Vec<(&str, u32)> = solution.words.iter()
+            .map(|word| (word.word.as_str(), word.score))
+            .collect();
+        assert_eq!(scored, vec![
+            ("PLANTED", 14),
+            ("PANTED", 6),
+            ("PLATED", 6),
+            ("ADEPT", 5),
+            ("APPLE", 5),
+            ("PEDAL", 5),
+            ("PLANT", 5),
+            ("DEAL", 1),
+            ("LEAD", 1),
+            ("TAPE", 1),
+        ]);
+        assert_eq!(solution.total_score, 49);
+        assert_eq!(solution.pangrams(), vec!["PLANTED"]);
+    }
+
+    #[test]
+    fn test_puzzle_errors() {
+        assert_eq!(
+            SpellingBee::new("PLANTS", 'a'),
+            Err(LetterPuzzleError::WrongLetterCount { expected: 7, found: 6 }),
+        );
+        assert_eq!(SpellingBee::new("PLANTED", 'z'), Err(LetterPuzzleError::CenterNotInLetters('Z')));
+        assert_eq!(SpellingBee::new("PLANTEE", 'a'), Err(LetterPuzzleError::RepeatedLetter('E')));
+        assert_eq!(SpellingBee::new("PLANT3D", 'a'), Err(LetterPuzzleError::InvalidLetter('3')));
+
+        assert_eq!(LetterBoxed::parse("FOA"), Err(LetterPuzzleError::TooFewSides(1)));
+        assert_eq!(LetterBoxed::parse("FOA LCV KHN SIA"), Err(LetterPuzzleError::RepeatedLetter('A')));
+    }
+
+    #[test]
+    fn test_letter_boxed() {
+        let solver = create_test_solver(&[
+            "flocks", "shaving", "flock", "kings", "snag", "foal", "cool", "zoo", "ink", "kin", "nag",
+        ]);
+        let puzzle = LetterBoxed::parse("FOA lcv KHN/SIG").unwrap();
+
+        let limits = ChainLimits { max_words: 3, max_chains: 10, time_limit: None };
+        let solution = solver.letter_boxed(&puzzle, &limits);
+        // KINGS ends G-S, FOAL starts F-O, COOL has O-O and INK has N-K,
+        // which are all on one side
+        assert_eq!(solution.words, vec!["FLOCKS", "SHAVING", "FLOCK", "SNAG", "KIN", "NAG"]);
+        assert_eq!(solution.chains, vec![vec!["FLOCKS", "SHAVING"]]);
+        assert!(!solution.timed_out);
+
+        let limits = ChainLimits { max_words: 1, ..ChainLimits::default() };
+        assert!(solver.letter_boxed(&puzzle, &limits).chains.is_empty());
+
+        // no time at all stops the search before it finds anything
+        let limits = ChainLimits { time_limit: Some(Duration::from_secs(0)), ..ChainLimits::default() };
+        let solution = solver.letter_boxed(&puzzle, &limits);
+        assert!(solution.timed_out);
+        assert!(solution.chains.is_empty());
+    }
+
+    #[test]
+    fn test_general_rules() {
+        let solver = create_test_solver(&["BANANA", "CABANA", "BAN", "NAB", "ABBA"]);
+        let rules = LetterSetRules {
+            allowed: LetterSet::parse("ABN").unwrap(),
+            required: LetterSet::parse("N").unwrap(),
+            min_word_length: 3,
+            sides: vec![],
+        };
+        assert_eq!(solver.words_matching(&rules), vec!["BANANA", "BAN", "NAB"]);
+
+        // B and N on one side, so BAN and NAB are fine but not ABBA's BB
+        let rules = LetterSetRules {
+            allowed: LetterSet::parse("ABN").unwrap(),
+            required: LetterSet::default(),
+            min_word_length: 3,
+            sides: vec![LetterSet::parse("BN").unwrap()],
+        };
+        assert_eq!(solver.words_matching(&rules), vec!["BANANA", "BAN", "NAB"]);
+    }
+}
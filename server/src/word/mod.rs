@@ -1 +1,9 @@
-pub mod word_index;
\ No newline at end of file
+pub mod word_index;
+pub mod word_set;
+pub mod query;
+pub mod wordle;
+pub mod guess_ranker;
+pub mod multi_board;
+pub mod crossword;
+pub mod letter_puzzle;
+pub mod analyst;
@@ -0,0 +1,329 @@
+use super::guess_ranker::GuessRanker;
+use super::wordle::{
+    Feedback,
+    GuessResult,
+    LetterFeedback,
+    WordleError,
+    WordleSolver,
+    normalize_word,
+};
+
+/// The Wordle variants played on several boards at once, where every guess
+/// goes on every board that isn't solved yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiBoardVariant {
+    Dordle,
+    Quordle,
+    Octordle,
+    Sedecordle,
+}
+
+impl MultiBoardVariant {
+    pub fn from_name(name: &str) -> Option<MultiBoardVariant> {
+        match name {
+            "dordle" => Some(MultiBoardVariant::Dordle),
+            "quordle" => Some(MultiBoardVariant::Quordle),
+            "octordle" => Some(MultiBoardVariant::Octordle),
+            "sedecordle" => Some(MultiBoardVariant::Sedecordle),
+            _ => None,
+        }
+    }
+
+    pub fn board_count(&self) -> usize {
+        match self {
+            MultiBoardVariant::Dordle => 2,
+            MultiBoardVariant::Quordle => 4,
+            MultiBoardVariant::Octordle => 8,
+            MultiBoardVariant::Sedecordle => 16,
+        }
+    }
+
+    pub fn max_guesses(&self) -> usize {
+        match self {
+            MultiBoardVariant::Dordle => 7,
+            MultiBoardVariant::Quordle => 9,
+            MultiBoardVariant::Octordle => 13,
+            MultiBoardVariant::Sedecordle => 21,
+        }
+    }
+}
+
+/// The guesses made so far and the feedback each board gave them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiBoardGame {
+    guesses: Vec<String>,
+    // each board's feedback, up to the guess that solved it
+    histories: Vec<Vec<GuessResult>>,
+    solutions: Vec<Option<String>>,
+}
+
+impl MultiBoardGame {
+    pub fn new(board_count: usize) -> MultiBoardGame {
+        assert!(board_count > 0, "a game needs at least one board");
+
+        MultiBoardGame {
+            guesses: vec![],
+            histories: vec![vec![]; board_count],
+            solutions: vec![None; board_count],
+        }
+    }
+
+    pub fn for_variant(variant: MultiBoardVariant) -> MultiBoardGame {
+        MultiBoardGame::new(variant.board_count())
+    }
+
+    pub fn board_count(&self) -> usize {
+        self.histories.len()
+    }
+
+    pub fn guesses(&self) -> &[String] {
+        &self.guesses
+    }
+
+    pub fn history(&self, board: usize) -> &[GuessResult] {
+        &self.histories[board]
+    }
+
+    /// The answer to `board`, once a guess has turned it all green.
+    pub fn solution(&self, board: usize) -> Option<&str> {
+        self.solutions[board].as_deref()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.solutions.iter().all(|solution| solution.is_some())
+    }
+
+    /// Records `guess` with one entry of `feedback` per board. Solved boards
+    /// don't show feedback any more, so theirs can be `None` and is ignored.
+    pub fn apply_guess(&mut self, guess: &str, feedback: &[Option<Feedback>]) -> Result<(), WordleError> {
+        let guess = normalize_word(guess)?;
+        if feedback.len() != self.board_count() {
+            return Err(WordleError::WrongBoardCount {
+                expected: self.board_count(),
+                found: feedback.len(),
+            });
+        }
+        let missing = (0..self.board_count())
+            .find(|board| self.solutions[*board].is_none() && feedback[*board].is_none());
+        if let Some(board) = missing {
+            return Err(WordleError::MissingFeedback(board));
+        }
+
+        let boards = self.solutions.iter_mut().zip(self.histories.iter_mut());
+        for ((solution, history), board_feedback) in boards.zip(feedback.iter()) {
+            if let (None, Some(board_feedback)) = (&solution, board_feedback) {
+                if board_feedback.iter().all(|letter| *letter == LetterFeedback::Green) {
+                    *solution = Some(guess.clone());
+                }
+                history.push(GuessResult::new(&guess, *board_feedback)?);
+            }
+        }
+        self.guesses.push(guess);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiBoardSuggestion {
+    pub guess: String,
+    /// The information the guess is expected to give, in bits, summed over
+    /// the boards that aren't solved yet.
+    pub combined_entropy: f64,
+    /// The boards down to one candidate, where that candidate is the guess.
+    pub certain_solves: usize,
+    /// The number of boards the guess is expected to solve.
+    pub expected_solves: f64,
+}
+
+/// Works out candidates and next guesses for multi-board games, sharing one
+/// answer list and feedback matrix between all the boards.
+#[derive(Debug)]
+pub struct MultiBoardSolver {
+    solver: WordleSolver,
+    ranker: GuessRanker,
+}
+
+impl MultiBoardSolver {
+    pub fn new(answers: Vec<String>, extra_guesses: &[String]) -> Result<MultiBoardSolver, WordleError> {
+        let solver = WordleSolver::new(answers)?;
+        let ranker = GuessRanker::new(solver.answers(), extra_guesses)?;
+
+        Ok(MultiBoardSolver {
+            solver,
+            ranker,
+        })
+    }
+
+    /// The possible answers for each board. A solved board's only candidate
+    /// is its answer.
+    pub fn candidates(&self, game: &MultiBoardGame) -> Vec<Vec<String>> {
+        (0..game.board_count())
+            .map(|board| match game.solution(board) {
+                Some(solution) => vec![solution.to_owned()],
+                None => self.solver.candidates(game.history(board)),
+            })
+            .collect()
+    }
+
+    /// The `count` best next guesses, best first. A guess that's sure to
+    /// solve a board comes first, as it has to be played at some point, then
+    /// the most combined information. Solved boards count for nothing.
+    pub fn suggest(&self, game: &MultiBoardGame, count: usize) -> Result<Vec<MultiBoardSuggestion>, WordleError> {
+        let candidates = self.candidates(game);
+        let mut suggestions: Vec<MultiBoardSuggestion> = vec![];
+
+        for board in (0..game.board_count()).filter(|b| game.solution(*b).is_none()) {
+            let board_candidates = &candidates[board];
+            let evaluations = self.ranker.evaluate_all(board_candidates)?;
+            if suggestions.is_empty() {
+                suggestions = evaluations.iter()
+                    .map(|evaluation| MultiBoardSuggestion {
+                        guess: evaluation.guess.clone(),
+                        combined_entropy: 0.0,
+                        certain_solves: 0,
+                        expected_solves: 0.0,
+                    })
+                    .collect();
+            }
+
+            for (suggestion, evaluation) in suggestions.iter_mut().zip(evaluations.iter()) {
+                suggestion.combined_entropy += evaluation.entropy;
+                if evaluation.is_candidate {
+                    suggestion.expected_solves += 1.0 / board_candidates.len() as f64;
+                    if board_candidates.len() == 1 {
+                        suggestion.certain_solves += 1;
+                    }
+                }
+            }
+        }
+
+        suggestions.sort_by(|a, b| {
+            b.certain_solves.cmp(&a.certain_solves)
+                .then_with(|| b.combined_entropy.total_cmp(&a.combined_entropy))
+                .then_with(|| b.expected_solves.total_cmp(&a.expected_solves))
+                .then_with(|| a.guess.cmp(&b.guess))
+        });
+        suggestions.truncate(count);
+
+        Ok(suggestions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word::wordle::score_guess;
+
+    const ANSWERS: [&str; 12] = [
+        "BATCH", "CATCH", "HATCH", "LATCH", "MATCH", "PATCH",
+        "CRANE", "CRATE", "GRATE", "IRATE", "SLATE", "STALE",
+    ];
+
+    fn create_test_solver() -> MultiBoardSolver {
+        MultiBoardSolver::new(
+            ANSWERS.iter().map(|w| w.to_string()).collect(),
+            &["BLIMP".to_owned()],
+        ).unwrap()
+    }
+
+    // feedback for `guess` on each board, or None once a board is solved
+    fn play(game: &mut MultiBoardGame, guess: &str, answers: &[&str]) {
+        let feedback: Vec<Option<Feedback>> = answers.iter()
+            .enumerate()
+            .map(|(board, answer)| match game.solution(board) {
+                Some(_) => None,
+                None => Some(score_guess(guess, answer)),
+            })
+            .collect();
+
+        game.apply_guess(guess, &feedback).unwrap();
+    }
+
+    #[test]
+    fn test_feedback_per_board() {
+        let solver = create_test_solver();
+        let answers = ["MATCH", "CRATE", "SLATE", "IRATE"];
+        let mut game = MultiBoardGame::for_variant(MultiBoardVariant::Quordle);
+
+        play(&mut game, "CRATE", &answers);
+        assert_eq!(game.solution(1), Some("CRATE"));
+        assert!(!game.is_over());
+
+        let candidates = solver.candidates(&game);
+        assert_eq!(candidates[1], vec!["CRATE"]);
+        for (board, answer) in answers.iter().enumerate() {
+            assert!(candidates[board].contains(&answer.to_string()));
+        }
+        // C, A and T are yellow, so it's an _ATCH word that doesn't start with C
+        assert_eq!(candidates[0], vec!["BATCH", "HATCH", "LATCH", "MATCH", "PATCH"]);
+        assert_eq!(candidates[3], vec!["GRATE", "IRATE"]);
+
+        // solved boards take no more feedback
+        play(&mut game, "IRATE", &answers);
+        assert_eq!(game.history(1).len(), 1);
+        assert_eq!(game.history(3).len(), 2);
+        assert_eq!(game.guesses(), ["CRATE", "IRATE"]);
+
+        play(&mut game, "SLATE", &answers);
+        play(&mut game, "MATCH", &answers);
+        assert!(game.is_over());
+    }
+
+    #[test]
+    fn test_suggest_weights_solved_boards_to_zero() {
+        let solver = create_test_solver();
+        let mut game = MultiBoardGame::new(2);
+        play(&mut game, "CATCH", &["CATCH", "LATCH"]);
+
+        let mut single = MultiBoardGame::new(1);
+        play(&mut single, "CATCH", &["LATCH"]);
+
+        // with board one solved, only board two counts
+        assert_eq!(solver.suggest(&game, 5).unwrap(), solver.suggest(&single, 5).unwrap());
+
+        let suggestions = solver.suggest(&game, 1).unwrap();
+        assert_eq!(suggestions[0].guess, "BLIMP");
+        assert_eq!(suggestions[0].certain_solves, 0);
+    }
+
+    #[test]
+    fn test_suggest_plays_certain_answers_first() {
+        let solver = create_test_solver();
+        let answers = ["MATCH", "STALE"];
+        let mut game = MultiBoardGame::new(2);
+        play(&mut game, "SLATE", &answers);
+
+        // STALE is the only candidate left on board two, and guessing it
+        // beats guessing BLIMP for board one
+        assert_eq!(solver.candidates(&game)[1], vec!["STALE"]);
+        let suggestions = solver.suggest(&game, 3).unwrap();
+        assert_eq!(suggestions[0].guess, "STALE");
+        assert_eq!(suggestions[0].certain_solves, 1);
+        assert!(suggestions[1].combined_entropy > suggestions[0].combined_entropy);
+
+        play(&mut game, "STALE", &answers);
+        play(&mut game, "MATCH", &answers);
+        assert!(solver.suggest(&game, 3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_apply_guess_errors() {
+        let mut game = MultiBoardGame::new(2);
+        let feedback = Some(score_guess("CRANE", "CRATE"));
+
+        assert_eq!(
+            game.apply_guess("CRANE", &[feedback]),
+            Err(WordleError::WrongBoardCount { expected: 2, found: 1 }),
+        );
+        assert_eq!(game.apply_guess("CRANE", &[feedback, None]), Err(WordleError::MissingFeedback(1)));
+        assert_eq!(
+            game.apply_guess("CRAN", &[feedback, feedback]),
+            Err(WordleError::InvalidWord("CRAN".to_owned())),
+        );
+        assert!(game.guesses().is_empty());
+
+        assert_eq!(MultiBoardVariant::from_name("octordle").map(|v| v.board_count()), Some(8));
+        assert_eq!(MultiBoardVariant::Sedecordle.max_guesses(), 21);
+    }
+}
@@ -0,0 +1,313 @@
+//! A small language for word index queries, e.g.
+//! `s1 & a3 & t5 & (h2 | h4) & !e & contains(r)`.
+//!
+//! - `a3` - an A in slot 3, counting from 1
+//! - `e` or `contains(e)` - an E in any slot
+//! - `!x` - words that don't match `x`
+//! - `x & y`, `x | y` - both, either
+//!
+//! `!` binds tightest, then `&`, then `|`, and parentheses group. Letters can
+//! be either case and spaces are ignored.
+
+use std::fmt;
+
+use super::word_index::{
+    AndNode,
+    ContainsNode,
+    FixedNode,
+    MAX_WORD_LENGTH,
+    NotNode,
+    OrNode,
+    QueryNode,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryErrorKind {
+    UnexpectedCharacter(char),
+    UnexpectedEnd,
+    /// The position is the opening parenthesis.
+    UnclosedParenthesis,
+    /// The slot and the word length it has to fit in.
+    InvalidSlot(String, usize),
+    UnknownFunction(String),
+    InvalidTerm(String),
+}
+
+/// What's wrong with a query and where, as the number of characters before
+/// the problem.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryParseError {
+    pub position: usize,
+    pub kind: QueryErrorKind,
+}
+
+impl fmt::Display for QueryParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            QueryErrorKind::UnexpectedCharacter(ch) => {
+                write!(formatter, "unexpected '{}'", ch)?
+            },
+            QueryErrorKind::UnexpectedEnd => {
+                write!(formatter, "the query ends too soon")?
+            },
+            QueryErrorKind::UnclosedParenthesis => {
+                write!(formatter, "'(' is never closed")?
+            },
+            QueryErrorKind::InvalidSlot(slot, word_length) => {
+                write!(formatter, "slot {} is not between 1 and {}", slot, word_length)?
+            },
+            QueryErrorKind::UnknownFunction(name) => {
+                write!(formatter, "unknown function '{}', try contains(x)", name)?
+            },
+            QueryErrorKind::InvalidTerm(term) => {
+                write!(formatter, "'{}' should be a letter, a letter and slot like a3, or contains(x)", term)?
+            },
+        }
+
+        write!(formatter, " at position {}", self.position)
+    }
+}
+
+/// Parses a query for words of any length the index supports.
+pub fn parse_query(query: &str) -> Result<QueryNode, QueryParseError> {
+    parse_query_for_length(query, MAX_WORD_LENGTH)
+}
+
+/// Parses a query for `word_length` letter words, so slots past the end of
+/// the word are errors.
+pub fn parse_query_for_length(query: &str, word_length: usize) -> Result<QueryNode, QueryParseError> {
+    let mut parser = Parser {
+        chars: query.chars().collect(),
+        position: 0,
+        word_length,
+    };
+
+    let node = parser.parse_or()?;
+    match parser.peek() {
+        None => Ok(node),
+        Some(ch) => Err(parser.error(QueryErrorKind::UnexpectedCharacter(ch))),
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    word_length: usize,
+}
+
+impl Parser {
+    // The next character that isn't a space, without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        while self.position < self.chars.len() && self.chars[self.position].is_whitespace() {
+            self.position += 1;
+        }
+
+        self.chars.get(self.position).copied()
+    }
+
+    // Consumes characters for as long as `matches` holds, spaces included.
+    fn take_while<F>(&mut self, matches: F) -> String
+        where F: Fn(char) -> bool,
+    {
+        let start = self.position;
+        while self.position < self.chars.len() && matches(self.chars[self.position]) {
+            self.position += 1;
+        }
+
+        self.chars[start..self.position].iter().collect()
+    }
+
+    fn error(&self, kind: QueryErrorKind) -> QueryParseError {
+        self.error_at(self.position, kind)
+    }
+
+    fn error_at(&self, position: usize, kind: QueryErrorKind) -> QueryParseError {
+        QueryParseError {
+            position,
+            kind,
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), QueryParseError> {
+        match self.peek() {
+            Some(ch) if ch == expected => {
+                self.position += 1;
+                Ok(())
+            },
+            Some(ch) => Err(self.error(QueryErrorKind::UnexpectedCharacter(ch))),
+            None => Err(self.error(QueryErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<QueryNode, QueryParseError> {
+        let mut node = self.parse_and()?;
+        while self.peek() == Some('|') {
+            self.position += 1;
+            node = OrNode(node, self.parse_and()?);
+        }
+
+        Ok(node)
+    }
+
+    fn parse_and(&mut self) -> Result<QueryNode, QueryParseError> {
+        let mut node = self.parse_unary()?;
+        while self.peek() == Some('&') {
+            self.position += 1;
+            node = AndNode(node, self.parse_unary()?);
+        }
+
+        Ok(node)
+    }
+
+    fn parse_unary(&mut self) -> Result<QueryNode, QueryParseError> {
+        if self.peek() == Some('!') {
+            self.position += 1;
+            Ok(NotNode(self.parse_unary()?))
+        } else {
+            self.parse_primary()
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<QueryNode, QueryParseError> {
+        match self.peek() {
+            Some('(') => {
+                let open = self.position;
+                self.position += 1;
+                let node = self.parse_or()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.position += 1;
+                        Ok(node)
+                    },
+                    Some(ch) => Err(self.error(QueryErrorKind::UnexpectedCharacter(ch))),
+                    None => Err(self.error_at(open, QueryErrorKind::UnclosedParenthesis)),
+                }
+            },
+            Some(ch) if ch.is_ascii_alphabetic() => self.parse_term(),
+            Some(ch) => Err(self.error(QueryErrorKind::UnexpectedCharacter(ch))),
+            None => Err(self.error(QueryErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    // A letter, a letter with a slot, or contains(letter).
+    fn parse_term(&mut self) -> Result<QueryNode, QueryParseError> {
+        let start = self.position;
+        let word = self.take_while(|ch| ch.is_ascii_alphabetic());
+
+        if word.len() == 1 {
+            let letter = word.chars().next().unwrap();
+            let slot_start = self.position;
+            let slot = self.take_while(|ch| ch.is_ascii_digit());
+            if slot.is_empty() {
+                return Ok(ContainsNode(letter));
+            }
+
+            return match slot.parse::<usize>() {
+                Ok(number) if (1..=self.word_length).contains(&number) => Ok(FixedNode(letter, number)),
+                _ => Err(self.error_at(slot_start, QueryErrorKind::InvalidSlot(slot, self.word_length))),
+            };
+        }
+
+        if self.peek() != Some('(') {
+            return Err(self.error_at(start, QueryErrorKind::InvalidTerm(word)));
+        }
+        if !word.eq_ignore_ascii_case("contains") {
+            return Err(self.error_at(start, QueryErrorKind::UnknownFunction(word)));
+        }
+
+        self.position += 1;
+        let letter = match self.peek() {
+            Some(ch) if ch.is_ascii_alphabetic() => ch,
+            Some(ch) => return Err(self.error(QueryErrorKind::UnexpectedCharacter(ch))),
+            None => return Err(self.error(QueryErrorKind::UnexpectedEnd)),
+        };
+        self.position += 1;
+        self.expect(')')?;
+
+        Ok(ContainsNode(letter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word::word_index::WordIndexN;
+
+    fn error_of(query: &str) -> (usize, QueryErrorKind) {
+        let error = parse_query(query).unwrap_err();
+        (error.position, error.kind)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse_query("s1 & a3 & t5 & (h2 | h4) & !e & contains(r)").unwrap(),
+            AndNode(
+                AndNode(
+                    AndNode(
+                        AndNode(
+                            AndNode(FixedNode('s', 1), FixedNode('a', 3)),
+                            FixedNode('t', 5),
+                        ),
+                        OrNode(FixedNode('h', 2), FixedNode('h', 4)),
+                    ),
+                    NotNode(ContainsNode('e')),
+                ),
+                ContainsNode('r'),
+            ),
+        );
+
+        // ! before & before |
+        assert_eq!(
+            parse_query("!a1&B2|c").unwrap(),
+            OrNode(AndNode(NotNode(FixedNode('a', 1)), FixedNode('b', 2)), ContainsNode('c')),
+        );
+        assert_eq!(parse_query(" CONTAINS ( z ) ").unwrap(), ContainsNode('z'));
+        assert_eq!(parse_query("!!k11").unwrap(), NotNode(NotNode(FixedNode('k', 11))));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(error_of(""), (0, QueryErrorKind::UnexpectedEnd));
+        assert_eq!(error_of("a1 &"), (4, QueryErrorKind::UnexpectedEnd));
+        assert_eq!(error_of("a1 & & b2"), (5, QueryErrorKind::UnexpectedCharacter('&')));
+        assert_eq!(error_of("a1 b2"), (3, QueryErrorKind::UnexpectedCharacter('b')));
+        assert_eq!(error_of("a1 & (b2 | c3"), (5, QueryErrorKind::UnclosedParenthesis));
+        assert_eq!(error_of("a1)"), (2, QueryErrorKind::UnexpectedCharacter(')')));
+        assert_eq!(error_of("a0"), (1, QueryErrorKind::InvalidSlot("0".to_owned(), MAX_WORD_LENGTH)));
+        assert_eq!(error_of("x & a12"), (5, QueryErrorKind::InvalidSlot("12".to_owned(), MAX_WORD_LENGTH)));
+        assert_eq!(
+            parse_query_for_length("b2 | a7", 5).unwrap_err(),
+            QueryParseError { position: 6, kind: QueryErrorKind::InvalidSlot("7".to_owned(), 5) },
+        );
+        assert_eq!(error_of("has(r)"), (0, QueryErrorKind::UnknownFunction("has".to_owned())));
+        assert_eq!(error_of("a & ab"), (4, QueryErrorKind::InvalidTerm("ab".to_owned())));
+        assert_eq!(error_of("contains(rs)"), (10, QueryErrorKind::UnexpectedCharacter('s')));
+        assert_eq!(error_of("contains(3)"), (9, QueryErrorKind::UnexpectedCharacter('3')));
+        assert_eq!(error_of("é"), (0, QueryErrorKind::UnexpectedCharacter('é')));
+
+        assert_eq!(
+            parse_query("a1 & (b2").unwrap_err().to_string(),
+            "'(' is never closed at position 5",
+        );
+    }
+
+    #[test]
+    fn test_query_index() {
+        let words = ["SWATH", "SHALT", "SHAFT", "SLANT", "SMART", "START", "SPATE"];
+        let index = WordIndexN::new(5, words.iter().map(|w| w.to_string()).collect()).unwrap();
+
+        assert_eq!(index.query("s1 & a3 & t5 & (h2 | h4)").unwrap(), vec!["SHALT", "SHAFT"]);
+        assert_eq!(index.query("s1 & a3 & t5 & (h2 | h4) & !l").unwrap(), vec!["SHAFT"]);
+        assert_eq!(index.query("s1 & a3 & t5 & (h2 | h4) & !e & contains(l)").unwrap(), vec!["SHALT"]);
+        assert_eq!(index.query("contains(r)").unwrap(), vec!["SMART", "START"]);
+        assert_eq!(index.query("!t").unwrap(), Vec::<String>::new());
+        assert_eq!(index.query("e | w").unwrap(), vec!["SWATH", "SPATE"]);
+        assert_eq!(index.query("a12").unwrap_err().kind, QueryErrorKind::InvalidSlot("12".to_owned(), 5));
+
+        // slots past the end of the index's words
+        let error = index.query("s1 & a7").unwrap_err();
+        assert_eq!((error.position, error.kind), (6, QueryErrorKind::InvalidSlot("7".to_owned(), 5)));
+        assert_eq!(index.evaluate_query(FixedNode('a', 7)), Vec::<String>::new());
+    }
+}
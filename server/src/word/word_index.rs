@@ -1,28 +1,39 @@
 use std::collections::HashMap;
+use std::fmt;
 use std::hash::Hash;
 
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
 
+use super::query::{QueryParseError, parse_query_for_length};
+use super::word_set::WordSet;
+
+pub const MIN_WORD_LENGTH: usize = 4;
+pub const MAX_WORD_LENGTH: usize = 11;
+
 trait WordIndex {
     fn list_matching_words(&self, query: QueryNode) -> Vec<String>;
 }
 
-#[derive(Debug)]
-enum QueryNode {
+#[derive(Debug, PartialEq)]
+pub enum QueryNode {
     Op(Box<QueryOp>),
     Location(LetterLocation),
+    /// Words that don't match the inner query.
+    Not(Box<QueryNode>),
+    /// Words with the letter in any slot.
+    Contains(Letter),
 }
 
-#[derive(Debug)]
-struct QueryOp {
+#[derive(Debug, PartialEq)]
+pub struct QueryOp {
     kind: QueryOpKind,
     left: QueryNode,
     right: QueryNode,
 }
 
-fn AndNode(left: QueryNode, right: QueryNode) -> QueryNode {
+pub fn AndNode(left: QueryNode, right: QueryNode) -> QueryNode {
     QueryNode::Op(Box::new(QueryOp {
         kind: QueryOpKind::And,
         left,
@@ -30,7 +41,7 @@ fn AndNode(left: QueryNode, right: QueryNode) -> QueryNode {
     }))
 }
 
-fn OrNode(left: QueryNode, right: QueryNode) -> QueryNode {
+pub fn OrNode(left: QueryNode, right: QueryNode) -> QueryNode {
     QueryNode::Op(Box::new(QueryOp {
         kind: QueryOpKind::Or,
         left,
@@ -38,32 +49,46 @@ fn OrNode(left: QueryNode, right: QueryNode) -> QueryNode {
     }))
 }
 
-fn FixedNode(letter: char, slot: usize) -> QueryNode {
+pub fn FixedNode(letter: char, slot: usize) -> QueryNode {
     QueryNode::Location(LetterLocation {
         letter: Letter::from_char(letter).unwrap(),
         slot: LetterSlot::from_usize(slot - 1).unwrap(),
     })
 }
 
-#[derive(Debug)]
-enum QueryOpKind {
+pub fn NotNode(inner: QueryNode) -> QueryNode {
+    QueryNode::Not(Box::new(inner))
+}
+
+pub fn ContainsNode(letter: char) -> QueryNode {
+    QueryNode::Contains(Letter::from_char(letter).unwrap())
+}
+
+#[derive(Debug, PartialEq)]
+pub enum QueryOpKind {
     And,
     Or,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
-struct LetterLocation {
+pub struct LetterLocation {
     letter: Letter,
     slot: LetterSlot, 
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
-enum LetterSlot {
+pub enum LetterSlot {
     One,
     Two,
     Three,
     Four,
     Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Eleven,
 }
 
 impl LetterSlot {
@@ -74,28 +99,31 @@ impl LetterSlot {
             2 => Ok(LetterSlot::Three),
             3 => Ok(LetterSlot::Four),
             4 => Ok(LetterSlot::Five),
+            5 => Ok(LetterSlot::Six),
+            6 => Ok(LetterSlot::Seven),
+            7 => Ok(LetterSlot::Eight),
+            8 => Ok(LetterSlot::Nine),
+            9 => Ok(LetterSlot::Ten),
+            10 => Ok(LetterSlot::Eleven),
             _ => Err(LetterSlotError { slot_as_usize: x }),
         }
     }
 
-    fn values() -> Vec<LetterSlot> {
-        vec![
-            LetterSlot::One,
-            LetterSlot::Two,
-            LetterSlot::Three,
-            LetterSlot::Four,
-            LetterSlot::Five,
-        ]
+    /// The first `word_length` slots.
+    fn values(word_length: usize) -> Vec<LetterSlot> {
+        (0..word_length)
+            .map(|x| LetterSlot::from_usize(x).unwrap())
+            .collect()
     }
 }
 
 #[derive(Debug)]
-struct LetterSlotError {
+pub struct LetterSlotError {
     slot_as_usize: usize,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
-enum Letter {
+pub enum Letter {
     A,
     B,
     C,
@@ -190,7 +218,7 @@ impl Letter {
 }
 
 #[derive(Debug)]
-struct LetterError {
+pub struct LetterError {
     letter_as_char: char
 }
 
@@ -204,116 +232,225 @@ enum EvaluationNode {
     Evaluated(Vec<String>),
 }
 
+/// Why a word list couldn't be indexed. Lines count from 1, and for a list
+/// that didn't come from a file they're the word's position in the list.
 #[derive(Debug)]
-struct WordIndexFive {
-    letter_location_map: HashMap<LetterLocation, Vec<String>>,
+pub enum WordListError {
+    Io(io::Error),
+    UnsupportedLength(usize),
+    WrongLength { line: usize, word: String, expected: usize },
+    InvalidLetter { line: usize, word: String, letter: char },
+}
+
+impl fmt::Display for WordListError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WordListError::Io(e) => {
+                write!(formatter, "couldn't read word list: {}", e)
+            },
+            WordListError::UnsupportedLength(length) => {
+                write!(
+                    formatter,
+                    "can't index {} letter words, only {} to {}",
+                    length,
+                    MIN_WORD_LENGTH,
+                    MAX_WORD_LENGTH,
+                )
+            },
+            WordListError::WrongLength { line, word, expected } => {
+                write!(formatter, "line {}: '{}' is not {} letters long", line, word, expected)
+            },
+            WordListError::InvalidLetter { line, word, letter } => {
+                write!(formatter, "line {}: '{}' has '{}', which is not a letter from A to Z", line, word, letter)
+            },
+        }
+    }
+}
+
+impl From<io::Error> for WordListError {
+    fn from(e: io::Error) -> WordListError {
+        WordListError::Io(e)
+    }
 }
 
-impl WordIndexFive {
-    fn new(word_list: Vec<String>) -> WordIndexFive {
-        let mut letter_location_map: HashMap<LetterLocation, Vec<String>> = HashMap::new();
+/// Lists words by the letter in each of their slots, for words of one
+/// length between `MIN_WORD_LENGTH` and `MAX_WORD_LENGTH`.
+///
+/// Words are known by their position in the word list, and the words with
+/// each letter in each slot are kept as a `WordSet` of those ids, so queries
+/// are worked out with bitwise operations and only turned back into words at
+/// the end.
+#[derive(Debug)]
+pub struct WordIndexN {
+    word_length: usize,
+    words: Vec<String>,
+    letter_location_map: HashMap<LetterLocation, WordSet>,
+    // the words with each letter in any slot
+    letter_map: HashMap<Letter, WordSet>,
+}
+
+impl WordIndexN {
+    /// Every word must be `word_length` letters from A to Z, in either case.
+    pub fn new(word_length: usize, word_list: Vec<String>) -> Result<WordIndexN, WordListError> {
+        if !(MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&word_length) {
+            return Err(WordListError::UnsupportedLength(word_length));
+        }
+
+        let mut letter_location_map: HashMap<LetterLocation, WordSet> = HashMap::new();
+        let mut letter_map: HashMap<Letter, WordSet> = HashMap::new();
         for letter in Letter::values() {
-            for slot in LetterSlot::values() {
+            for slot in LetterSlot::values(word_length) {
                 letter_location_map.insert(
                     LetterLocation {
                         letter,
                         slot,
                     },
-                    vec![],
+                    WordSet::empty(word_list.len()),
                 );
             }
+            letter_map.insert(letter, WordSet::empty(word_list.len()));
         }
 
-        for word in word_list {
-            for (index, ch) in word.char_indices() {
-                let slot = LetterSlot::from_usize(index).unwrap();
-                let letter = Letter::from_char(ch).unwrap();
+        for (index, word) in word_list.iter().enumerate() {
+            let letters = letters_of(word, index + 1)?;
+            if letters.len() != word_length {
+                return Err(WordListError::WrongLength {
+                    line: index + 1,
+                    word: word.to_owned(),
+                    expected: word_length,
+                });
+            }
 
+            for (slot, letter) in LetterSlot::values(word_length).into_iter().zip(letters) {
                 let key = LetterLocation {
                     letter,
                     slot,
                 };
 
-                letter_location_map.get_mut(&key).map(|v| v.push(word.to_owned()));
+                if let Some(words) = letter_location_map.get_mut(&key) {
+                    words.insert(index);
+                }
+                if let Some(words) = letter_map.get_mut(&letter) {
+                    words.insert(index);
+                }
             }
         }
 
-        WordIndexFive {
+        Ok(WordIndexN {
+            word_length,
+            words: word_list,
             letter_location_map,
-        }
+            letter_map,
+        })
     }
 
-    fn evaluate_query(&self, query: QueryNode) -> Vec<String> {
-        match query {
-            QueryNode::Location(loc) => {
-                let vec_copy: Vec<String> = self.letter_location_map
-                    .get(&loc)
-                    .map(|v| v.to_vec())
-                    .unwrap_or(vec![]);
-
-                vec_copy
-            },
-            QueryNode::Op(op) => {
-                let l = self.evaluate_query(op.left);
-                let r = self.evaluate_query(op.right);
-
-                match op.kind {
-                    QueryOpKind::And => list_intersection(l, r),
-                    QueryOpKind::Or => list_union(l, r),
-                }
-            },
+    /// Indexes the `word_length` letter words in a file with one word per
+    /// line, so one list can be shared between lengths. Blank lines are
+    /// skipped, and any other line that isn't a word is an error.
+    pub fn from_file<P>(word_list_file_path: P, word_length: usize) -> Result<WordIndexN, WordListError>
+        where P: AsRef<Path>,
+    {
+        let mut word_list: Vec<String> = vec![];
+
+        for (index, line) in read_lines(word_list_file_path)?.enumerate() {
+            let line = line?;
+            let word = line.trim();
+            if !word.is_empty() && letters_of(word, index + 1)?.len() == word_length {
+                word_list.push(word.to_owned());
+            }
         }
+
+        WordIndexN::new(word_length, word_list)
     }
-}
 
-impl WordIndex for WordIndexFive {
-    fn list_matching_words(&self, query: QueryNode) -> Vec<String> {
-        self.evaluate_query(query)
+    pub fn word_length(&self) -> usize {
+        self.word_length
     }
-}
 
-fn list_intersection(l1: Vec<String>, l2: Vec<String>) -> Vec<String> {
-    let mut combined: Vec<String> = vec![];
+    pub fn word_count(&self) -> usize {
+        self.words.len()
+    }
 
-    for w in l1 {
-        if l2.contains(&w) {
-            combined.push(w.to_owned());
-        }
+    /// The word with the given id, its position in the word list.
+    pub fn word(&self, id: usize) -> &str {
+        &self.words[id]
     }
 
-    combined
-}
+    /// The ids of the words with `letter` in `slot`, counting from 1 as
+    /// `FixedNode` does, or `None` if `letter` isn't A to Z or `slot` isn't
+    /// in this index's words.
+    pub fn letter_location_set(&self, letter: char, slot: usize) -> Option<&WordSet> {
+        let location = LetterLocation {
+            letter: Letter::from_char(letter).ok()?,
+            slot: LetterSlot::from_usize(slot.checked_sub(1)?).ok()?,
+        };
 
-fn list_union(l1: Vec<String>, l2: Vec<String>) -> Vec<String> {
-    let mut combined: Vec<String> = vec![];
+        self.letter_location_map.get(&location)
+    }
 
-    for w in l1 {
-        combined.push(w.to_owned());
+    /// Parses `query` for this index's word length and lists the words
+    /// matching it.
+    pub fn query(&self, query: &str) -> Result<Vec<String>, QueryParseError> {
+        Ok(self.evaluate_query(parse_query_for_length(query, self.word_length)?))
     }
 
-    for w in l2 {
-        if !combined.contains(&w) {
-            combined.push(w.to_owned());
-        }
+    /// The matching words, in word list order.
+    pub fn evaluate_query(&self, query: QueryNode) -> Vec<String> {
+        self.words_in(&self.matching_set(&query))
     }
 
-    combined
-}
+    /// The ids of the matching words, which are their positions in the word
+    /// list.
+    pub fn matching_set(&self, query: &QueryNode) -> WordSet {
+        match query {
+            // slots past the end of the words match nothing
+            QueryNode::Location(loc) => self.letter_location_map.get(loc)
+                .cloned()
+                .unwrap_or_else(|| WordSet::empty(self.words.len())),
+            QueryNode::Op(op) => {
+                let mut set = self.matching_set(&op.left);
+                let right = self.matching_set(&op.right);
 
+                match op.kind {
+                    QueryOpKind::And => set.intersect_with(&right),
+                    QueryOpKind::Or => set.union_with(&right),
+                }
+                set
+            },
+            QueryNode::Not(inner) => {
+                let mut set = self.matching_set(inner);
+                set.invert();
+                set
+            },
+            QueryNode::Contains(letter) => self.letter_map.get(letter)
+                .cloned()
+                .unwrap_or_else(|| WordSet::empty(self.words.len())),
+        }
+    }
 
-fn create_word_list_from_file(
-    word_list_file_path: &str,
-) -> io::Result<Vec<String>> {
-    let mut word_list: Vec<String> = vec![];
-    let lines = read_lines(word_list_file_path)?;
+    pub fn words_in(&self, set: &WordSet) -> Vec<String> {
+        set.ids()
+            .map(|id| self.words[id].clone())
+            .collect()
+    }
+}
 
-    for line_result in lines {
-        if let Ok(line) = line_result {
-            word_list.push(line);
-        }
+impl WordIndex for WordIndexN {
+    fn list_matching_words(&self, query: QueryNode) -> Vec<String> {
+        self.evaluate_query(query)
     }
+}
 
-    Ok(word_list)
+fn letters_of(word: &str, line: usize) -> Result<Vec<Letter>, WordListError> {
+    word.chars()
+        .map(|ch| {
+            Letter::from_char(ch).map_err(|_| WordListError::InvalidLetter {
+                line,
+                word: word.to_owned(),
+                letter: ch,
+            })
+        })
+        .collect()
 }
 
 // The output is wrapped in a Result to allow matching on errors
@@ -389,16 +526,47 @@ mod tests {
 
     #[test]
     fn test_create_word_index_five() {
-        let word_index = WordIndexFive::new(create_word_list());
+        let word_index = WordIndexN::new(5, create_word_list()).unwrap();
+
+        assert_eq!(word_index.word_count(), create_word_list().len());
+
+        let a_second = &word_index.letter_location_map[&LetterLocation {
+            letter: Letter::A,
+            slot: LetterSlot::Two,
+        }];
+        let expected: Vec<String> = create_word_list().into_iter()
+            .filter(|word| word.as_bytes()[1] == b'A')
+            .collect();
+        assert!(!expected.is_empty() && expected.len() < word_index.word_count());
+        assert_eq!(word_index.words_in(a_second), expected);
+
+        let d_last = &word_index.letter_location_map[&LetterLocation {
+            letter: Letter::D,
+            slot: LetterSlot::Five,
+        }];
+        let expected: Vec<String> = create_word_list().into_iter()
+            .filter(|word| word.ends_with('D'))
+            .collect();
+        assert!(!expected.is_empty() && expected.len() < word_index.word_count());
+        assert_eq!(word_index.words_in(d_last), expected);
+    }
 
-        // for (k, v) in word_index.letter_location_map.iter() {
-        //     println!("{:?} : {:?}", k, v);
-        // }
+    #[test]
+    fn test_letter_location_set() {
+        let word_index = WordIndexN::new(5, create_word_list()).unwrap();
+
+        let b_first = word_index.letter_location_set('b', 1).unwrap();
+        assert_eq!(word_index.words_in(b_first), vec!["BAAED", "BAALS"]);
+        assert_eq!(word_index.letter_location_set('B', 1), Some(b_first));
+
+        assert_eq!(word_index.letter_location_set('B', 0), None);
+        assert_eq!(word_index.letter_location_set('B', 6), None);
+        assert_eq!(word_index.letter_location_set('3', 1), None);
     }
 
     #[test]
     fn test_evaluate_query() {
-        let word_index = WordIndexFive::new(create_word_list());
+        let word_index = WordIndexN::new(5, create_word_list()).unwrap();
 
         let query = QueryNode::Op(Box::new(QueryOp {
             kind: QueryOpKind::And,
@@ -425,8 +593,7 @@ mod tests {
 
     #[test]
     fn try_quordle() {
-        let word_list = create_word_list_from_file("/Users/dwadeson/games_workspace/word_resources/scrabble_words_2019_5.txt").unwrap();
-        let word_index = WordIndexFive::new(word_list);
+        let word_index = WordIndexN::from_file("/Users/dwadeson/games_workspace/word_resources/scrabble_words_2019_5.txt", 5).unwrap();
         let query = AndNode(
             FixedNode('s', 1),
             AndNode(
@@ -445,4 +612,78 @@ mod tests {
 
         println!("{:?}", matching_words);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_results_in_word_list_order() {
+        let word_index = WordIndexN::new(5, create_word_list()).unwrap();
+
+        // the right hand side's words come first in the list
+        let query = OrNode(FixedNode('z', 1), FixedNode('b', 3));
+        assert_eq!(
+            word_index.evaluate_query(query),
+            vec!["CABAL", "DABBA", "FABBY", "FABLE", "GABBA", "GABBY", "JABOT", "KABAB",
+                 "LABDA", "RABAT", "RABBI", "SABAL", "XEBEC", "YABAS", "ZABRA", "ZACKS"],
+        );
+
+        let query = AndNode(FixedNode('a', 2), NotNode(OrNode(ContainsNode('s'), ContainsNode('e'))));
+        let set = word_index.matching_set(&query);
+        assert_eq!(set.len(), 17);
+        assert_eq!(word_index.words_in(&set)[..3], ["AALII", "CABAL", "DABBA"]);
+    }
+
+    #[test]
+    fn test_other_lengths() {
+        let four = WordIndexN::new(4, vec!["SASH".to_owned(), "BASH".to_owned(), "SHAH".to_owned()]).unwrap();
+        assert_eq!(four.word_length(), 4);
+        assert_eq!(
+            four.evaluate_query(AndNode(FixedNode('a', 2), FixedNode('h', 4))),
+            vec!["SASH", "BASH"],
+        );
+
+        let eleven = WordIndexN::new(11, vec!["abracadabra".to_owned(), "Anglophiles".to_owned()]).unwrap();
+        assert_eq!(eleven.evaluate_query(FixedNode('s', 11)), vec!["Anglophiles"]);
+        assert_eq!(eleven.evaluate_query(FixedNode('a', 11)), vec!["abracadabra"]);
+    }
+
+    #[test]
+    fn test_bad_word_lists() {
+        assert!(matches!(
+            WordIndexN::new(12, vec![]),
+            Err(WordListError::UnsupportedLength(12)),
+        ));
+        assert!(matches!(
+            WordIndexN::new(3, vec![]),
+            Err(WordListError::UnsupportedLength(3)),
+        ));
+
+        match WordIndexN::new(5, vec!["CRANE".to_owned(), "CRANES".to_owned()]) {
+            Err(WordListError::WrongLength { line: 2, word, expected: 5 }) => assert_eq!(word, "CRANES"),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        // non-ASCII letters are counted as letters, not bytes
+        match WordIndexN::new(5, vec!["CRÈME".to_owned()]) {
+            Err(WordListError::InvalidLetter { line: 1, letter: 'È', .. }) => {},
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_file() {
+        let path = std::env::temp_dir().join("word_index_test_from_file.txt");
+
+        std::fs::write(&path, "crane\n\n  SLATE \nspeedy\ntop\n").unwrap();
+        let word_index = WordIndexN::from_file(&path, 5).unwrap();
+        assert_eq!(word_index.evaluate_query(FixedNode('e', 5)), vec!["crane", "SLATE"]);
+
+        std::fs::write(&path, "crane\nit-s\nslate\n").unwrap();
+        let error = WordIndexN::from_file(&path, 5).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(error.to_string(), "line 2: 'it-s' has '-', which is not a letter from A to Z");
+
+        assert!(matches!(
+            WordIndexN::from_file("/no/such/word_list.txt", 5),
+            Err(WordListError::Io(_)),
+        ));
+    }
+}
@@ -0,0 +1,155 @@
+const BLOCK_BITS: usize = 64;
+
+/// A set of word ids below a fixed size, one bit per id, so that AND, OR and
+/// NOT work a block of 64 words at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordSet {
+    blocks: Vec<u64>,
+    // the ids run from 0 to size - 1
+    size: usize,
+}
+
+impl WordSet {
+    pub fn empty(size: usize) -> WordSet {
+        WordSet {
+            blocks: vec![0; size.div_ceil(BLOCK_BITS)],
+            size,
+        }
+    }
+
+    pub fn full(size: usize) -> WordSet {
+        let mut set = WordSet::empty(size);
+        set.invert();
+        set
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn insert(&mut self, id: usize) {
+        assert!(id < self.size, "word id {} is out of range for {} words", id, self.size);
+        self.blocks[id / BLOCK_BITS] |= 1 << (id % BLOCK_BITS);
+    }
+
+    pub fn remove(&mut self, id: usize) {
+        if id < self.size {
+            self.blocks[id / BLOCK_BITS] &= !(1 << (id % BLOCK_BITS));
+        }
+    }
+
+    pub fn contains(&self, id: usize) -> bool {
+        id < self.size && self.blocks[id / BLOCK_BITS] & (1 << (id % BLOCK_BITS)) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.iter().map(|block| block.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.iter().all(|block| *block == 0)
+    }
+
+    /// Whether the sets have any ids in common.
+    pub fn intersects(&self, other: &WordSet) -> bool {
+        self.check_size(other);
+        self.blocks.iter()
+            .zip(other.blocks.iter())
+            .any(|(block, other_block)| block & other_block != 0)
+    }
+
+    pub fn intersect_with(&mut self, other: &WordSet) {
+        self.check_size(other);
+        for (block, other_block) in self.blocks.iter_mut().zip(other.blocks.iter()) {
+            *block &= *other_block;
+        }
+    }
+
+    pub fn union_with(&mut self, other: &WordSet) {
+        self.check_size(other);
+        for (block, other_block) in self.blocks.iter_mut().zip(other.blocks.iter()) {
+            *block |= *other_block;
+        }
+    }
+
+    /// Swaps the ids in the set for the ones that weren't.
+    pub fn invert(&mut self) {
+        for block in self.blocks.iter_mut() {
+            *block = !*block;
+        }
+
+        // the bits past the last id stay clear
+        let used = self.size % BLOCK_BITS;
+        if used != 0 {
+            let last = self.blocks.len() - 1;
+            self.blocks[last] &= (1 << used) - 1;
+        }
+    }
+
+    /// The ids in the set, smallest first.
+    pub fn ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.blocks.iter()
+            .enumerate()
+            .flat_map(|(index, block)| {
+                let mut bits = *block;
+                std::iter::from_fn(move || {
+                    if bits == 0 {
+                        return None;
+                    }
+
+                    let bit = bits.trailing_zeros() as usize;
+                    bits &= bits - 1;
+                    Some(index * BLOCK_BITS + bit)
+                })
+            })
+    }
+
+    fn check_size(&self, other: &WordSet) {
+        assert_eq!(self.size, other.size, "word sets over different word lists");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_of(size: usize, ids: &[usize]) -> WordSet {
+        let mut set = WordSet::empty(size);
+        for id in ids {
+            set.insert(*id);
+        }
+        set
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let mut set = set_of(130, &[0, 3, 64, 129]);
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(64));
+        assert!(!set.contains(65));
+        assert!(!set.contains(500));
+
+        set.union_with(&set_of(130, &[1, 3, 100]));
+        assert_eq!(set.ids().collect::<Vec<usize>>(), vec![0, 1, 3, 64, 100, 129]);
+
+        assert!(set.intersects(&set_of(130, &[2, 100])));
+        assert!(!set.intersects(&set_of(130, &[2, 99])));
+        set.intersect_with(&set_of(130, &[3, 100, 128]));
+        assert_eq!(set.ids().collect::<Vec<usize>>(), vec![3, 100]);
+
+        set.remove(100);
+        set.remove(500);
+        assert_eq!(set.ids().collect::<Vec<usize>>(), vec![3]);
+        set.insert(100);
+
+        // inverting doesn't add ids past the end
+        set.invert();
+        assert_eq!(set.len(), 128);
+        assert!(!set.contains(130));
+        assert_eq!(WordSet::full(130).ids().last(), Some(129));
+
+        assert!(WordSet::empty(0).is_empty());
+        assert!(WordSet::full(0).is_empty());
+        assert_eq!(WordSet::full(64).len(), 64);
+    }
+}
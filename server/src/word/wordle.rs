@@ -0,0 +1,296 @@
+use std::fmt;
+
+use super::word_index::{AndNode, FixedNode, WordIndexN};
+
+pub const WORD_LENGTH: usize = 5;
+
+/// The colour Wordle shows for one letter of a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LetterFeedback {
+    /// The answer has this letter here.
+    Green,
+    /// The answer has this letter somewhere else.
+    Yellow,
+    /// The answer has no more of this letter than the guess has greens and
+    /// yellows for it.
+    Gray,
+}
+
+pub type Feedback = [LetterFeedback; WORD_LENGTH];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WordleError {
+    InvalidWord(String),
+    InvalidFeedback(String),
+    UnknownAnswer(String),
+    WrongBoardCount { expected: usize, found: usize },
+    /// No feedback was given for a board that isn't solved yet.
+    MissingFeedback(usize),
+}
+
+impl fmt::Display for WordleError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WordleError::InvalidWord(word) => {
+                write!(formatter, "'{}' is not a {} letter word", word, WORD_LENGTH)
+            },
+            WordleError::InvalidFeedback(feedback) => {
+                write!(
+                    formatter,
+                    "'{}' is not {} letters of feedback (g for green, y for yellow, b, x, - or . for gray)",
+                    feedback,
+                    WORD_LENGTH,
+                )
+            },
+            WordleError::UnknownAnswer(word) => {
+                write!(formatter, "'{}' is not in the answer list", word)
+            },
+            WordleError::WrongBoardCount { expected, found } => {
+                write!(formatter, "expected feedback for {} boards, got {}", expected, found)
+            },
+            WordleError::MissingFeedback(board) => {
+                write!(formatter, "board {} isn't solved, so it needs feedback", board + 1)
+            },
+        }
+    }
+}
+
+/// A guess and the feedback it got. The guess is always a `WORD_LENGTH`
+/// letter upper case word.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuessResult {
+    guess: String,
+    feedback: Feedback,
+}
+
+impl GuessResult {
+    /// Upper cases `guess`, checking it's `WORD_LENGTH` letters.
+    pub fn new(guess: &str, feedback: Feedback) -> Result<GuessResult, WordleError> {
+        Ok(GuessResult {
+            guess: normalize_word(guess)?,
+            feedback,
+        })
+    }
+
+    /// Reads feedback written one character per letter: `g` for green, `y`
+    /// for yellow and `b`, `x`, `-` or `.` for gray, e.g. `GUESS` and `-gy..`.
+    pub fn parse(guess: &str, feedback: &str) -> Result<GuessResult, WordleError> {
+        Ok(GuessResult {
+            guess: normalize_word(guess)?,
+            feedback: parse_feedback(feedback)?,
+        })
+    }
+
+    pub fn guess(&self) -> &str {
+        &self.guess
+    }
+
+    pub fn feedback(&self) -> Feedback {
+        self.feedback
+    }
+}
+
+/// The feedback Wordle gives for `guess` when the answer is `answer`. Both
+/// must be upper case words of `WORD_LENGTH` letters.
+///
+/// Greens are marked first. Then each other letter of the guess, left to
+/// right, is yellow while the answer has copies of it not yet matched, and
+/// gray after that.
+pub fn score_guess(guess: &str, answer: &str) -> Feedback {
+    let guess = guess.as_bytes();
+    let answer = answer.as_bytes();
+    let mut feedback = [LetterFeedback::Gray; WORD_LENGTH];
+    let mut unmatched = [0usize; 26];
+
+    for slot in 0..WORD_LENGTH {
+        if guess[slot] == answer[slot] {
+            feedback[slot] = LetterFeedback::Green;
+        } else {
+            unmatched[letter_index(answer[slot])] += 1;
+        }
+    }
+
+    for slot in 0..WORD_LENGTH {
+        let letter = letter_index(guess[slot]);
+        if feedback[slot] != LetterFeedback::Green && unmatched[letter] > 0 {
+            feedback[slot] = LetterFeedback::Yellow;
+            unmatched[letter] -= 1;
+        }
+    }
+
+    feedback
+}
+
+pub fn parse_feedback(feedback: &str) -> Result<Feedback, WordleError> {
+    let invalid = || WordleError::InvalidFeedback(feedback.to_owned());
+    if feedback.chars().count() != WORD_LENGTH {
+        return Err(invalid());
+    }
+
+    let mut parsed = [LetterFeedback::Gray; WORD_LENGTH];
+    for (slot, ch) in feedback.chars().enumerate() {
+        parsed[slot] = match ch.to_ascii_lowercase() {
+            'g' => LetterFeedback::Green,
+            'y' => LetterFeedback::Yellow,
+            'b' | 'x' | '-' | '.' => LetterFeedback::Gray,
+            _ => return Err(invalid()),
+        };
+    }
+
+    Ok(parsed)
+}
+
+/// Upper cases `word`, checking it's `WORD_LENGTH` letters.
+pub fn normalize_word(word: &str) -> Result<String, WordleError> {
+    let normalized = word.trim().to_ascii_uppercase();
+    if normalized.len() == WORD_LENGTH && normalized.bytes().all(|b| b.is_ascii_uppercase()) {
+        Ok(normalized)
+    } else {
+        Err(WordleError::InvalidWord(word.to_owned()))
+    }
+}
+
+fn letter_index(letter: u8) -> usize {
+    (letter - b'A') as usize
+}
+
+/// Narrows a list of possible answers down to the ones that fit the
+/// feedback from a game so far.
+#[derive(Debug)]
+pub struct WordleSolver {
+    // upper case, in the order given, which is also their order in the index
+    answers: Vec<String>,
+    index: WordIndexN,
+}
+
+impl WordleSolver {
+    pub fn new(answers: Vec<String>) -> Result<WordleSolver, WordleError> {
+        let answers = answers.iter()
+            .map(|word| normalize_word(word))
+            .collect::<Result<Vec<String>, WordleError>>()?;
+
+        Ok(WordleSolver {
+            index: WordIndexN::new(WORD_LENGTH, answers.clone()).expect("answers were checked above"),
+            answers,
+        })
+    }
+
+    pub fn answers(&self) -> &[String] {
+        &self.answers
+    }
+
+    /// Every answer that would have given exactly the feedback in
+    /// `history`, in the order the answers were given.
+    pub fn candidates(&self, history: &[GuessResult]) -> Vec<String> {
+        // the index narrows things down by the greens, then the rest of the
+        // feedback, duplicate letters and all, is checked word by word
+        let green_query = history.iter()
+            .flat_map(|result| {
+                result.guess.chars()
+                    .zip(result.feedback.iter())
+                    .enumerate()
+                    .filter(|(_, (_, feedback))| **feedback == LetterFeedback::Green)
+                    .map(|(slot, (letter, _))| (letter, slot))
+            })
+            .map(|(letter, slot)| FixedNode(letter, slot + 1))
+            .reduce(AndNode);
+
+        let fits_history = |word: &String| {
+            history.iter().all(|result| score_guess(&result.guess, word) == result.feedback)
+        };
+
+        match green_query {
+            Some(query) => self.index.matching_set(&query)
+                .ids()
+                .map(|id| &self.answers[id])
+                .filter(|word| fits_history(word))
+                .cloned()
+                .collect(),
+            None => self.answers.iter()
+                .filter(|word| fits_history(word))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use LetterFeedback::{Gray, Green, Yellow};
+
+    fn create_test_solver() -> WordleSolver {
+        WordleSolver::new([
+            "abide", "bidet", "deuce", "edged", "eider", "oldie", "olive", "irate",
+            "crane", "hello", "robot", "level", "llama", "floor", "speed", "lotus",
+        ].iter().map(|w| w.to_string()).collect()).unwrap()
+    }
+
+    #[test]
+    fn test_score_guess() {
+        assert_eq!(score_guess("CRANE", "CRANE"), [Green; 5]);
+        assert_eq!(score_guess("FLOOR", "ROBOT"), [Gray, Gray, Yellow, Green, Yellow]);
+        // both Ls are yellow, as HELLO has two
+        assert_eq!(score_guess("LLAMA", "HELLO"), [Yellow, Yellow, Gray, Gray, Gray]);
+        // the green E uses up HELLO's only E
+        assert_eq!(score_guess("LEVEL", "HELLO"), [Yellow, Green, Gray, Gray, Yellow]);
+        // the first E is yellow, and the second gray
+        assert_eq!(score_guess("SPEED", "ABIDE"), [Gray, Gray, Yellow, Gray, Yellow]);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            GuessResult::parse("speed", "..Y-y").unwrap(),
+            GuessResult {
+                guess: "SPEED".to_owned(),
+                feedback: [Gray, Gray, Yellow, Gray, Yellow],
+            },
+        );
+        assert_eq!(parse_feedback("gybxG").unwrap(), [Green, Yellow, Gray, Gray, Green]);
+
+        assert_eq!(parse_feedback("gyb"), Err(WordleError::InvalidFeedback("gyb".to_owned())));
+        assert_eq!(parse_feedback("gybzz"), Err(WordleError::InvalidFeedback("gybzz".to_owned())));
+        assert_eq!(GuessResult::parse("spee", "....."), Err(WordleError::InvalidWord("spee".to_owned())));
+        assert_eq!(GuessResult::parse("sp3ed", "....."), Err(WordleError::InvalidWord("sp3ed".to_owned())));
+        assert!(WordleSolver::new(vec!["toolong".to_owned()]).is_err());
+
+        let result = GuessResult::new("crane", [Green; WORD_LENGTH]).unwrap();
+        assert_eq!(result.guess(), "CRANE");
+        assert_eq!(result.feedback(), [Green; WORD_LENGTH]);
+        assert_eq!(GuessResult::new("cranes", [Gray; WORD_LENGTH]), Err(WordleError::InvalidWord("cranes".to_owned())));
+    }
+
+    #[test]
+    fn test_candidates_with_duplicate_letters() {
+        let solver = create_test_solver();
+
+        // one E, not in the middle or fourth, and a D somewhere but last
+        let history = vec![GuessResult::parse("SPEED", "..y.y").unwrap()];
+        assert_eq!(solver.candidates(&history), vec!["ABIDE", "OLDIE"]);
+
+        // a gray for the second of two guessed letters caps the count, it
+        // doesn't rule the letter out, so LLAMA's second L rules it out
+        let history = vec![GuessResult::parse("LEVEL", "g....").unwrap()];
+        assert_eq!(solver.candidates(&history), vec!["LOTUS"]);
+    }
+
+    #[test]
+    fn test_candidates_narrow_to_answer() {
+        let solver = create_test_solver();
+
+        for answer in solver.answers().iter() {
+            let history: Vec<GuessResult> = ["CRANE", "OLDIE"].iter()
+                .map(|guess| GuessResult::new(guess, score_guess(guess, answer)).unwrap())
+                .collect();
+
+            let candidates = solver.candidates(&history);
+            assert!(candidates.contains(answer), "{}", answer);
+            assert!(candidates.iter().all(|word| {
+                history.iter().all(|result| score_guess(result.guess(), word) == result.feedback())
+            }));
+        }
+
+        assert_eq!(solver.candidates(&[]).len(), solver.answers().len());
+    }
+}